@@ -0,0 +1,349 @@
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use error::{Error, Result};
+use input;
+use runner::DayRun;
+use solver::Part;
+
+/// Where the recorded answers live when nothing else is configured
+pub const DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+
+/// The known answers for each day, as read from a manifest like
+///
+/// ```toml
+/// [day01]
+/// part1 = 411
+/// part2 = 56360
+///
+/// [day02]
+/// part2 = "umdryabviapkozistwcnihjqx"
+/// ```
+///
+/// Only tables named `dayNN` holding `part1`/`part2` integers or strings are
+/// understood.
+#[derive(Debug, Default, PartialEq)]
+pub struct AnswerManifest {
+    days: BTreeMap<u32, Expected>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Expected {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl AnswerManifest {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<AnswerManifest> {
+        let contents = input::read_file(&path)?;
+
+        contents.parse().map_err(|err: Error| err.in_file(path))
+    }
+
+    /// The recorded answer for one part of a day
+    pub fn expected(&self, day: u32, part: Part) -> Option<&str> {
+        let expected = self.days.get(&day)?;
+        let answer = match part {
+            Part::One => &expected.part1,
+            Part::Two => &expected.part2,
+        };
+
+        answer.as_ref().map(|a| a.as_str())
+    }
+
+    /// Compares the answers from a run against the recorded ones
+    pub fn check(&self, run: &DayRun) -> Vec<PartCheck> {
+        [Part::One, Part::Two]
+            .iter()
+            .map(|part| {
+                let expected = self.expected(run.day, *part).map(String::from);
+                let actual = match run.outcome {
+                    Ok(ref answers) => match *part {
+                        Part::One => answers.part1.clone(),
+                        Part::Two => answers.part2.clone(),
+                    },
+                    Err(ref err) => {
+                        return PartCheck {
+                            day: run.day,
+                            part: *part,
+                            verdict: Verdict::Error(err.clone()),
+                        }
+                    }
+                };
+
+                let verdict = match (expected, actual) {
+                    (Some(ref expected), Some(ref actual)) if expected == actual => Verdict::Pass,
+                    (Some(expected), actual) => Verdict::Fail { expected, actual },
+                    (None, actual) => Verdict::Missing { actual },
+                };
+
+                PartCheck {
+                    day: run.day,
+                    part: *part,
+                    verdict,
+                }
+            })
+            .collect()
+    }
+}
+
+impl FromStr for AnswerManifest {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        lazy_static! {
+            static ref TABLE: Regex = Regex::new("^\\[\\s*day(\\d+)\\s*\\]\\s*(?:#.*)?$").unwrap();
+            static ref ENTRY: Regex =
+                Regex::new("^(part[12])\\s*=\\s*(?:\"([^\"]*)\"|(-?\\d+))\\s*(?:#.*)?$").unwrap();
+        }
+
+        let mut manifest = AnswerManifest::default();
+        let mut current: Option<u32> = None;
+
+        for (index, line) in s.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(capture) = TABLE.captures(line) {
+                let day = capture[1]
+                    .parse()
+                    .map_err(|_| Error::parse(line_number, 1, "day number is too large"))?;
+                current = Some(day);
+                manifest.days.entry(day).or_insert_with(Expected::default);
+                continue;
+            }
+
+            let capture = ENTRY.captures(line).ok_or_else(|| {
+                Error::parse(line_number, 1, format!("can't understand '{}'", line))
+            })?;
+
+            let day = current.ok_or_else(|| {
+                Error::parse(line_number, 1, "answer given before any [dayNN] table")
+            })?;
+
+            let value = capture
+                .get(2)
+                .or_else(|| capture.get(3))
+                .map(|m| String::from(m.as_str()))
+                .unwrap_or_default();
+
+            let expected = manifest.days.entry(day).or_insert_with(Expected::default);
+            if &capture[1] == "part1" {
+                expected.part1 = Some(value);
+            } else {
+                expected.part2 = Some(value);
+            }
+        }
+
+        Ok(manifest)
+    }
+}
+
+/// How one part of one day compared to its recorded answer
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartCheck {
+    pub day: u32,
+    pub part: Part,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// There's no recorded answer to compare with
+    Missing {
+        actual: Option<String>,
+    },
+    /// The day could not be solved at all
+    Error(String),
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        match *self {
+            Verdict::Fail { .. } | Verdict::Error(_) => true,
+            Verdict::Pass | Verdict::Missing { .. } => false,
+        }
+    }
+}
+
+/// Lists the verdict for every part followed by a summary line
+pub fn render_checks(checks: &[PartCheck]) -> String {
+    let mut out = String::new();
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut missing = 0;
+
+    for check in checks.iter() {
+        let part = match check.part {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+        write!(out, "day {:02} part {}: ", check.day, part).unwrap();
+
+        match check.verdict {
+            Verdict::Pass => {
+                passed += 1;
+                writeln!(out, "pass").unwrap();
+            }
+            Verdict::Fail {
+                ref expected,
+                ref actual,
+            } => {
+                failed += 1;
+                writeln!(
+                    out,
+                    "FAIL (expected {}, got {})",
+                    expected,
+                    actual.as_ref().map_or("nothing", |a| a.as_str())
+                )
+                .unwrap();
+            }
+            Verdict::Missing { ref actual } => {
+                missing += 1;
+                match *actual {
+                    Some(ref actual) => writeln!(out, "missing (got {})", actual).unwrap(),
+                    None => writeln!(out, "missing").unwrap(),
+                }
+            }
+            Verdict::Error(ref err) => {
+                failed += 1;
+                writeln!(out, "ERROR ({})", err).unwrap();
+            }
+        }
+    }
+
+    writeln!(
+        out,
+        "{} passed, {} failed, {} missing",
+        passed, failed, missing
+    )
+    .unwrap();
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solver::Answers;
+
+    fn manifest() -> AnswerManifest {
+        "# known answers
+[day01]
+part1 = 411
+part2 = \"56360\"   # quoted is fine too
+
+[day02]
+part2 = \"umdryabviapkozistwcnihjqx\"
+"
+        .parse()
+        .unwrap()
+    }
+
+    fn day_run(day: u32, part1: &str, part2: &str) -> DayRun {
+        DayRun {
+            day,
+            title: "",
+            outcome: Ok(Answers {
+                part1: Some(String::from(part1)),
+                part2: Some(String::from(part2)),
+            }),
+            timings: None,
+        }
+    }
+
+    #[test]
+    fn parse_reads_integers_and_strings() {
+        let manifest = manifest();
+
+        assert_eq!(Some("411"), manifest.expected(1, Part::One));
+        assert_eq!(Some("56360"), manifest.expected(1, Part::Two));
+        assert_eq!(None, manifest.expected(2, Part::One));
+        assert_eq!(
+            Some("umdryabviapkozistwcnihjqx"),
+            manifest.expected(2, Part::Two)
+        );
+    }
+
+    #[test]
+    fn parse_reports_line_of_error() {
+        let err = "[day01]\npart1 = 411\npart3 = 5\n"
+            .parse::<AnswerManifest>()
+            .unwrap_err();
+
+        match err {
+            Error::Parse { line, .. } => assert_eq!(3, line),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_needs_a_table_first() {
+        assert!("part1 = 4".parse::<AnswerManifest>().is_err());
+    }
+
+    #[test]
+    fn check_passes_fails_and_misses() {
+        let manifest = manifest();
+
+        let checks = manifest.check(&day_run(1, "411", "1"));
+        assert_eq!(Verdict::Pass, checks[0].verdict);
+        assert!(checks[1].verdict.is_failure());
+
+        let checks = manifest.check(&day_run(2, "5704", "umdryabviapkozistwcnihjqx"));
+        assert_eq!(
+            Verdict::Missing {
+                actual: Some(String::from("5704"))
+            },
+            checks[0].verdict
+        );
+        assert_eq!(Verdict::Pass, checks[1].verdict);
+    }
+
+    #[test]
+    fn check_reports_errors_for_both_parts() {
+        let run = DayRun {
+            day: 1,
+            title: "",
+            outcome: Err(String::from("no input")),
+            timings: None,
+        };
+
+        let checks = manifest().check(&run);
+
+        assert_eq!(2, checks.len());
+        assert!(checks.iter().all(|c| c.verdict.is_failure()));
+    }
+
+    #[test]
+    fn render_checks_summarizes() {
+        let checks = manifest().check(&day_run(1, "411", "1"));
+        let text = render_checks(&checks);
+
+        assert_eq!(
+            "day 01 part 1: pass\n\
+             day 01 part 2: FAIL (expected 56360, got 1)\n\
+             1 passed, 1 failed, 0 missing\n",
+            text
+        );
+    }
+
+    #[test]
+    fn recorded_answers_parse() {
+        let manifest = AnswerManifest::load(DEFAULT_ANSWERS_FILE).unwrap();
+
+        assert_eq!(Some("411"), manifest.expected(1, Part::One));
+    }
+}
@@ -0,0 +1,266 @@
+use std::fmt::Display;
+use std::fmt::Write;
+use std::time::Duration;
+
+use runner::{format_duration, json_string, nanos, Format};
+use solver::{Part, Solution, Timings};
+
+/// The spread of a set of timing samples
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarizes the samples, or `None` if there are none
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 1 {
+            sorted[middle]
+        } else {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        };
+
+        Some(Stats {
+            min: sorted[0],
+            median,
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+/// Timing statistics for each step of a day over repeated runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayBench {
+    pub day: u32,
+    pub title: &'static str,
+    pub runs: usize,
+    pub outcome: Result<BenchStats, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchStats {
+    pub parse: Stats,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+    pub total: Stats,
+}
+
+impl BenchStats {
+    fn from_timings(timings: &[Timings]) -> Option<BenchStats> {
+        let parse: Vec<Duration> = timings.iter().map(|t| t.parse).collect();
+        let part1: Vec<Duration> = timings.iter().filter_map(|t| t.part1).collect();
+        let part2: Vec<Duration> = timings.iter().filter_map(|t| t.part2).collect();
+        let total: Vec<Duration> = timings.iter().map(|t| t.total()).collect();
+
+        Some(BenchStats {
+            parse: Stats::from_samples(&parse)?,
+            part1: Stats::from_samples(&part1),
+            part2: Stats::from_samples(&part2),
+            total: Stats::from_samples(&total)?,
+        })
+    }
+}
+
+/// Solves a day `runs` times over the same input and summarizes the timings.
+/// Stops at the first failure.
+pub fn bench(solution: &dyn Solution, input: &str, part: Option<Part>, runs: usize) -> DayBench {
+    let mut timings = Vec::with_capacity(runs);
+
+    for _ in 0..runs {
+        match solution.solve_timed(input, part) {
+            Ok((_, t)) => timings.push(t),
+            Err(err) => return DayBench::failed(solution, runs, err),
+        }
+    }
+
+    DayBench {
+        day: solution.day(),
+        title: solution.title(),
+        runs,
+        outcome: BenchStats::from_timings(&timings).ok_or_else(|| String::from("No runs")),
+    }
+}
+
+impl DayBench {
+    pub fn failed<E: Display>(solution: &dyn Solution, runs: usize, err: E) -> DayBench {
+        DayBench {
+            day: solution.day(),
+            title: solution.title(),
+            runs,
+            outcome: Err(err.to_string()),
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+pub fn render(benches: &[DayBench], format: Format) -> String {
+    match format {
+        Format::Text => render_text(benches),
+        Format::Json => render_json(benches),
+    }
+}
+
+fn render_text(benches: &[DayBench]) -> String {
+    let mut out = String::new();
+
+    for bench in benches.iter() {
+        writeln!(
+            out,
+            "Day {}: {} ({} runs)",
+            bench.day, bench.title, bench.runs
+        )
+        .unwrap();
+
+        match bench.outcome {
+            Ok(ref stats) => {
+                write_stats(&mut out, "parse", &stats.parse);
+                if let Some(ref part1) = stats.part1 {
+                    write_stats(&mut out, "part 1", part1);
+                }
+                if let Some(ref part2) = stats.part2 {
+                    write_stats(&mut out, "part 2", part2);
+                }
+                write_stats(&mut out, "total", &stats.total);
+            }
+            Err(ref err) => writeln!(out, "error: {}", err).unwrap(),
+        }
+    }
+
+    out
+}
+
+fn write_stats(out: &mut String, name: &str, stats: &Stats) {
+    writeln!(
+        out,
+        "{:>8}: min {}  median {}  max {}",
+        name,
+        format_duration(stats.min),
+        format_duration(stats.median),
+        format_duration(stats.max)
+    )
+    .unwrap();
+}
+
+fn render_json(benches: &[DayBench]) -> String {
+    let days: Vec<String> = benches
+        .iter()
+        .map(|bench| {
+            let mut fields = vec![
+                format!("\"day\":{}", bench.day),
+                format!("\"title\":{}", json_string(bench.title)),
+                format!("\"runs\":{}", bench.runs),
+            ];
+
+            match bench.outcome {
+                Ok(ref stats) => {
+                    fields.push(format!("\"parse\":{}", json_stats(&stats.parse)));
+                    if let Some(ref part1) = stats.part1 {
+                        fields.push(format!("\"part1\":{}", json_stats(part1)));
+                    }
+                    if let Some(ref part2) = stats.part2 {
+                        fields.push(format!("\"part2\":{}", json_stats(part2)));
+                    }
+                    fields.push(format!("\"total\":{}", json_stats(&stats.total)));
+                }
+                Err(ref err) => fields.push(format!("\"error\":{}", json_string(err))),
+            }
+
+            format!("{{{}}}", fields.join(","))
+        })
+        .collect();
+
+    format!("[{}]\n", days.join(","))
+}
+
+fn json_stats(stats: &Stats) -> String {
+    format!(
+        "{{\"min_ns\":{},\"median_ns\":{},\"max_ns\":{}}}",
+        nanos(stats.min),
+        nanos(stats.median),
+        nanos(stats.max)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use day01::Day01;
+
+    fn ns(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_nanos(*v)).collect()
+    }
+
+    #[test]
+    fn stats_of_odd_samples() {
+        let stats = Stats::from_samples(&ns(&[30, 10, 20])).unwrap();
+
+        assert_eq!(Duration::from_nanos(10), stats.min);
+        assert_eq!(Duration::from_nanos(20), stats.median);
+        assert_eq!(Duration::from_nanos(30), stats.max);
+    }
+
+    #[test]
+    fn stats_of_even_samples_averages_middle() {
+        let stats = Stats::from_samples(&ns(&[40, 10, 20, 30])).unwrap();
+
+        assert_eq!(Duration::from_nanos(25), stats.median);
+    }
+
+    #[test]
+    fn stats_of_nothing() {
+        assert_eq!(None, Stats::from_samples(&[]));
+    }
+
+    #[test]
+    fn bench_times_selected_parts() {
+        let result = bench(&Day01, "+1\n-2\n+3\n+1\n", Some(Part::Two), 3);
+        let stats = result.outcome.unwrap();
+
+        assert_eq!(3, result.runs);
+        assert!(stats.part1.is_none());
+        assert!(stats.part2.is_some());
+    }
+
+    #[test]
+    fn bench_reports_failures() {
+        let result = bench(&Day01, "nope", None, 3);
+
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn render_json_lists_stats() {
+        let stats = Stats::from_samples(&ns(&[5])).unwrap();
+        let benches = vec![DayBench {
+            day: 1,
+            title: "Chronal Calibration",
+            runs: 1,
+            outcome: Ok(BenchStats {
+                parse: stats,
+                part1: None,
+                part2: None,
+                total: stats,
+            }),
+        }];
+
+        assert_eq!(
+            "[{\"day\":1,\"title\":\"Chronal Calibration\",\"runs\":1,\
+             \"parse\":{\"min_ns\":5,\"median_ns\":5,\"max_ns\":5},\
+             \"total\":{\"min_ns\":5,\"median_ns\":5,\"max_ns\":5}}]\n",
+            render(&benches, Format::Json)
+        );
+    }
+}
@@ -0,0 +1,456 @@
+use std::error;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use runner::Format;
+use solver::Part;
+
+pub const USAGE: &str = "Usage:
+    adventofcode run <days> [options]
+    adventofcode <days> [options]
+    adventofcode verify [<days>] [--answers <file>] [--inputs <dir>]
+    adventofcode bench <days> [--runs <n>] [--part <1|2>] [--inputs <dir>] [--format <text|json>]
+    adventofcode new-day <day> [--title <title>] [--src <dir>]
+    adventofcode help
+
+<days> is a single day (5), a range of days (3-7) or 'all'.
+
+Options:
+    --part <1|2>            only solve one part of each puzzle
+    --input <file>          read the input for a single day from <file>, or '-' for stdin
+    --inputs <dir>          directory holding the dayNN.txt inputs
+    --format <text|json>    how to print the answers (default: text)
+    --answers <file>        recorded answers to verify against (default: answers.toml)
+    --runs <n>              how many times to repeat each day when benchmarking (default: 10)
+    --title <title>         the puzzle title for a new day (default: Day <day>)
+    --src <dir>             source directory a new day is added to (default: src)";
+
+/// How many times `bench` repeats each day unless told otherwise
+pub const DEFAULT_BENCH_RUNS: usize = 10;
+
+/// The highest day in the advent calendar
+pub const LAST_DAY: u32 = 25;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Verify(VerifyOptions),
+    Bench(BenchOptions),
+    NewDay(NewDayOptions),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub days: Days,
+    pub part: Option<Part>,
+    pub input: Option<InputSource>,
+    pub inputs_dir: Option<PathBuf>,
+    pub format: Format,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifyOptions {
+    pub days: Days,
+    pub answers: Option<PathBuf>,
+    pub inputs_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub days: Days,
+    pub runs: usize,
+    pub part: Option<Part>,
+    pub inputs_dir: Option<PathBuf>,
+    pub format: Format,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NewDayOptions {
+    pub day: u32,
+    pub title: Option<String>,
+    pub src_dir: Option<PathBuf>,
+}
+
+/// Which days of the calendar to run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Days {
+    All,
+    Range(u32, u32),
+}
+
+impl Days {
+    pub fn contains(&self, day: u32) -> bool {
+        match *self {
+            Days::All => true,
+            Days::Range(first, last) => (first..=last).contains(&day),
+        }
+    }
+
+    fn single(&self) -> Option<u32> {
+        match *self {
+            Days::Range(first, last) if first == last => Some(first),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Days {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Days::All => f.write_str("all days"),
+            Days::Range(first, last) if first == last => write!(f, "day {}", first),
+            Days::Range(first, last) => write!(f, "days {}-{}", first, last),
+        }
+    }
+}
+
+impl FromStr for Days {
+    type Err = UsageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Days::All);
+        }
+
+        let mut bounds = s.splitn(2, '-');
+        let first = parse_day(bounds.next().unwrap_or(""))?;
+        let last = match bounds.next() {
+            Some(last) => parse_day(last)?,
+            None => first,
+        };
+
+        if first > last {
+            return Err(UsageError::new(format!("Day range '{}' runs backwards", s)));
+        }
+
+        Ok(Days::Range(first, last))
+    }
+}
+
+fn parse_day(s: &str) -> Result<u32, UsageError> {
+    match s.parse() {
+        Ok(day) if (1..=LAST_DAY).contains(&day) => Ok(day),
+        _ => Err(UsageError::new(format!(
+            "Can't parse '{}' as a day between 1 and {}",
+            s, LAST_DAY
+        ))),
+    }
+}
+
+/// Where to read the input from when it's not the usual file for the day
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+/// Parses the command line arguments, not including the program name
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, UsageError> {
+    let mut args = args.into_iter();
+
+    match args.next() {
+        None => Err(UsageError::new("Missing a command or day")),
+        Some(ref a) if a == "help" || a == "--help" || a == "-h" => Ok(Command::Help),
+        Some(ref a) if a == "run" => match args.next() {
+            Some(days) => parse_run(&days, args),
+            None => Err(UsageError::new("'run' needs the days to run")),
+        },
+        Some(ref a) if a == "verify" => parse_verify(args),
+        Some(ref a) if a == "bench" => match args.next() {
+            Some(days) => parse_bench(&days, args),
+            None => Err(UsageError::new("'bench' needs the days to run")),
+        },
+        Some(ref a) if a == "new-day" => match args.next() {
+            Some(day) => parse_new_day(&day, args),
+            None => Err(UsageError::new("'new-day' needs the day to create")),
+        },
+        Some(days) => parse_run(&days, args),
+    }
+}
+
+fn parse_run<I: Iterator<Item = String>>(days: &str, args: I) -> Result<Command, UsageError> {
+    let mut options = RunOptions {
+        days: days.parse()?,
+        part: None,
+        input: None,
+        inputs_dir: None,
+        format: Format::Text,
+    };
+
+    for (flag, value) in flags(args)? {
+        match flag.as_str() {
+            "--part" => options.part = Some(parse_part(&value)?),
+            "--input" if value == "-" => options.input = Some(InputSource::Stdin),
+            "--input" => options.input = Some(InputSource::File(PathBuf::from(value))),
+            "--inputs" => options.inputs_dir = Some(PathBuf::from(value)),
+            "--format" => options.format = parse_format(&value)?,
+            _ => return Err(UsageError::new(format!("Unknown option '{}'", flag))),
+        }
+    }
+
+    if options.input.is_some() && options.days.single().is_none() {
+        return Err(UsageError::new(
+            "--input can only be used with a single day",
+        ));
+    }
+
+    Ok(Command::Run(options))
+}
+
+fn parse_verify<I: Iterator<Item = String>>(args: I) -> Result<Command, UsageError> {
+    let mut args = args.peekable();
+    let mut options = VerifyOptions {
+        days: Days::All,
+        answers: None,
+        inputs_dir: None,
+    };
+
+    if let Some(days) = args.peek().cloned() {
+        if !days.starts_with("--") {
+            options.days = days.parse()?;
+            args.next();
+        }
+    }
+
+    for (flag, value) in flags(args)? {
+        match flag.as_str() {
+            "--answers" => options.answers = Some(PathBuf::from(value)),
+            "--inputs" => options.inputs_dir = Some(PathBuf::from(value)),
+            _ => return Err(UsageError::new(format!("Unknown option '{}'", flag))),
+        }
+    }
+
+    Ok(Command::Verify(options))
+}
+
+fn parse_bench<I: Iterator<Item = String>>(days: &str, args: I) -> Result<Command, UsageError> {
+    let mut options = BenchOptions {
+        days: days.parse()?,
+        runs: DEFAULT_BENCH_RUNS,
+        part: None,
+        inputs_dir: None,
+        format: Format::Text,
+    };
+
+    for (flag, value) in flags(args)? {
+        match flag.as_str() {
+            "--runs" => options.runs = parse_runs(&value)?,
+            "--part" => options.part = Some(parse_part(&value)?),
+            "--inputs" => options.inputs_dir = Some(PathBuf::from(value)),
+            "--format" => options.format = parse_format(&value)?,
+            _ => return Err(UsageError::new(format!("Unknown option '{}'", flag))),
+        }
+    }
+
+    Ok(Command::Bench(options))
+}
+
+fn parse_new_day<I: Iterator<Item = String>>(day: &str, args: I) -> Result<Command, UsageError> {
+    let mut options = NewDayOptions {
+        day: parse_day(day)?,
+        title: None,
+        src_dir: None,
+    };
+
+    for (flag, value) in flags(args)? {
+        match flag.as_str() {
+            "--title" => options.title = Some(value),
+            "--src" => options.src_dir = Some(PathBuf::from(value)),
+            _ => return Err(UsageError::new(format!("Unknown option '{}'", flag))),
+        }
+    }
+
+    Ok(Command::NewDay(options))
+}
+
+fn parse_runs(s: &str) -> Result<usize, UsageError> {
+    match s.parse() {
+        Ok(runs) if runs > 0 => Ok(runs),
+        _ => Err(UsageError::new(format!(
+            "Runs must be a positive number, not '{}'",
+            s
+        ))),
+    }
+}
+
+/// Pairs up each `--flag` with the value that follows it
+fn flags<I: Iterator<Item = String>>(mut args: I) -> Result<Vec<(String, String)>, UsageError> {
+    let mut result = vec![];
+
+    while let Some(flag) = args.next() {
+        match args.next() {
+            Some(value) => result.push((flag, value)),
+            None => return Err(UsageError::new(format!("{} needs a value", flag))),
+        }
+    }
+
+    Ok(result)
+}
+
+fn parse_part(s: &str) -> Result<Part, UsageError> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(UsageError::new(format!("Part must be 1 or 2, not '{}'", s))),
+    }
+}
+
+fn parse_format(s: &str) -> Result<Format, UsageError> {
+    match s {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(UsageError::new(format!(
+            "Format must be text or json, not '{}'",
+            s
+        ))),
+    }
+}
+
+/// The command line couldn't be understood
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageError {
+    message: String,
+}
+
+impl UsageError {
+    pub fn new<S: Into<String>>(message: S) -> UsageError {
+        UsageError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl error::Error for UsageError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(line: &str) -> Result<Command, UsageError> {
+        parse_args(line.split_whitespace().map(String::from))
+    }
+
+    fn run_options(line: &str) -> RunOptions {
+        match parse(line).unwrap() {
+            Command::Run(options) => options,
+            other => panic!("expected a run command, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn bare_day_runs_it() {
+        let options = run_options("01");
+
+        assert_eq!(Days::Range(1, 1), options.days);
+        assert_eq!(None, options.part);
+        assert_eq!(Format::Text, options.format);
+    }
+
+    #[test]
+    fn run_parses_range_and_all() {
+        assert_eq!(Days::Range(3, 7), run_options("run 3-7").days);
+        assert_eq!(Days::All, run_options("run all").days);
+    }
+
+    #[test]
+    fn run_parses_flags() {
+        let options = run_options("run 4 --part 2 --format json --input - --inputs puzzles");
+
+        assert_eq!(Some(Part::Two), options.part);
+        assert_eq!(Format::Json, options.format);
+        assert_eq!(Some(InputSource::Stdin), options.input);
+        assert_eq!(Some(PathBuf::from("puzzles")), options.inputs_dir);
+    }
+
+    #[test]
+    fn verify_defaults_to_all_days() {
+        let expected = VerifyOptions {
+            days: Days::All,
+            answers: None,
+            inputs_dir: None,
+        };
+
+        assert_eq!(Command::Verify(expected), parse("verify").unwrap());
+    }
+
+    #[test]
+    fn verify_parses_days_and_flags() {
+        let expected = VerifyOptions {
+            days: Days::Range(2, 4),
+            answers: Some(PathBuf::from("mine.toml")),
+            inputs_dir: None,
+        };
+
+        assert_eq!(
+            Command::Verify(expected),
+            parse("verify 2-4 --answers mine.toml").unwrap()
+        );
+        assert!(parse("verify --part 1").is_err());
+    }
+
+    #[test]
+    fn bench_parses_runs() {
+        match parse("bench all --runs 25 --format json").unwrap() {
+            Command::Bench(options) => {
+                assert_eq!(Days::All, options.days);
+                assert_eq!(25, options.runs);
+                assert_eq!(Format::Json, options.format);
+            }
+            other => panic!("expected a bench command, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn bench_needs_positive_runs() {
+        assert!(parse("bench 1 --runs 0").is_err());
+        assert!(parse("bench 1 --runs many").is_err());
+        assert!(parse("bench").is_err());
+    }
+
+    #[test]
+    fn new_day_parses_day_and_flags() {
+        let expected = NewDayOptions {
+            day: 10,
+            title: Some(String::from("Stars")),
+            src_dir: Some(PathBuf::from("lib")),
+        };
+
+        assert_eq!(
+            Command::NewDay(expected),
+            parse("new-day 10 --title Stars --src lib").unwrap()
+        );
+        assert!(parse("new-day").is_err());
+        assert!(parse("new-day 3-4").is_err());
+    }
+
+    #[test]
+    fn help_is_recognized() {
+        assert_eq!(Command::Help, parse("--help").unwrap());
+    }
+
+    #[test]
+    fn bad_arguments_are_usage_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("run").is_err());
+        assert!(parse("26").is_err());
+        assert!(parse("7-3").is_err());
+        assert!(parse("run 1 --part 3").is_err());
+        assert!(parse("run 1 --format xml").is_err());
+        assert!(parse("run 1 --part").is_err());
+        assert!(parse("run 1 --bogus 1").is_err());
+    }
+
+    #[test]
+    fn input_file_needs_a_single_day() {
+        assert!(parse("run all --input day.txt").is_err());
+        assert!(parse("run 2 --input day.txt").is_ok());
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use super::repeat::{find_repeat, find_repeats, Repeat};
+use error::{Error, Result};
+
+/// The time travel device's list of frequency changes, starting from 0
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrequencyDevice {
+    changes: Vec<i64>,
+}
+
+impl FrequencyDevice {
+    pub fn new(changes: Vec<i64>) -> FrequencyDevice {
+        FrequencyDevice { changes }
+    }
+
+    pub fn changes(&self) -> &[i64] {
+        &self.changes
+    }
+
+    /// The frequency after each change, over a single pass of the list
+    pub fn frequencies(&self) -> RunningSum<impl Iterator<Item = i64> + '_> {
+        running_sum(self.changes.iter().cloned())
+    }
+
+    /// The frequency after every change has been applied once, or an error
+    /// if it doesn't fit in an `i64`
+    pub fn final_frequency(&self) -> Result<i64> {
+        total(&self.changes)
+    }
+
+    /// Where a frequency is first reached twice when the list of changes is
+    /// applied over and over, or `None` if that never happens
+    pub fn find_repeat(&self) -> Result<Option<Repeat>> {
+        find_repeat(&self.changes)
+    }
+
+    /// The first `count` times a frequency is reached again, in order
+    pub fn find_repeats(&self, count: usize) -> Result<Vec<Repeat>> {
+        find_repeats(&self.changes, count)
+    }
+
+    /// The first frequency reached twice when the list of changes is applied
+    /// over and over
+    pub fn first_repeat(&self) -> Result<i64> {
+        self.find_repeat()?
+            .map(|repeat| repeat.frequency)
+            .ok_or_else(|| Error::domain("No frequency is ever reached twice"))
+    }
+}
+
+impl From<Vec<i64>> for FrequencyDevice {
+    fn from(changes: Vec<i64>) -> FrequencyDevice {
+        FrequencyDevice::new(changes)
+    }
+}
+
+impl FromIterator<i64> for FrequencyDevice {
+    fn from_iter<I: IntoIterator<Item = i64>>(changes: I) -> FrequencyDevice {
+        FrequencyDevice::new(changes.into_iter().collect())
+    }
+}
+
+/// Adds up a list of changes, or an error if the frequency overflows an
+/// `i64` on the way
+pub fn total(changes: &[i64]) -> Result<i64> {
+    changes
+        .iter()
+        .try_fold(0i64, |frequency, change| frequency.checked_add(*change))
+        .ok_or_else(|| Error::domain("frequency overflows i64"))
+}
+
+/// The frequency after each change in a stream of changes, starting from 0.
+/// The stream ends early if the frequency would overflow an `i64`.
+#[derive(Debug, Clone)]
+pub struct RunningSum<I> {
+    changes: I,
+    current: i64,
+}
+
+impl<I: Iterator<Item = i64>> Iterator for RunningSum<I> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let change = self.changes.next()?;
+        self.current = self.current.checked_add(change)?;
+
+        Some(self.current)
+    }
+}
+
+/// Follows the frequency through any stream of changes, like a live feed
+/// from the device
+pub fn running_sum<I: IntoIterator<Item = i64>>(changes: I) -> RunningSum<I::IntoIter> {
+    RunningSum {
+        changes: changes.into_iter(),
+        current: 0,
+    }
+}
+
+/// The first frequency reached twice in a stream of changes, counting the
+/// starting frequency of 0. Stops reading as soon as it's found, so the
+/// stream can be endless.
+pub fn first_repeat_in<I: IntoIterator<Item = i64>>(changes: I) -> Option<i64> {
+    let mut seen = HashSet::new();
+    seen.insert(0);
+
+    running_sum(changes).find(|frequency| !seen.insert(*frequency))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn frequencies_are_running_sum() {
+        let device = FrequencyDevice::new(vec![1, -2, 3, 1]);
+
+        assert_eq!(vec![1, -1, 2, 3], device.frequencies().collect::<Vec<_>>());
+        assert_eq!(3, device.final_frequency().unwrap());
+    }
+
+    #[test]
+    fn overflowing_frequency_is_an_error() {
+        let device = FrequencyDevice::new(vec![i64::MAX, 1]);
+
+        assert_eq!(
+            "frequency overflows i64",
+            device.final_frequency().unwrap_err().to_string()
+        );
+        assert!(device.first_repeat().is_err());
+        assert_eq!(vec![i64::MAX], device.frequencies().collect::<Vec<_>>());
+        assert_eq!(
+            Ok(i64::MIN),
+            total(&[i64::MIN + 1, -1]).map_err(|e| e.to_string())
+        );
+    }
+
+    #[test]
+    fn first_repeat_samples() {
+        let repeat = |changes: Vec<i64>| FrequencyDevice::new(changes).first_repeat().unwrap();
+
+        assert_eq!(2, repeat(vec![1, -2, 3, 1]));
+        assert_eq!(0, repeat(vec![1, -1]));
+        assert_eq!(10, repeat(vec![3, 3, 4, -2, -4]));
+        assert_eq!(5, repeat(vec![-6, 3, 8, 5, -6]));
+        assert_eq!(14, repeat(vec![7, 7, -2, -7, -4]));
+    }
+
+    #[test]
+    fn first_repeat_without_one_is_an_error() {
+        assert!(FrequencyDevice::new(vec![1]).first_repeat().is_err());
+        assert!(FrequencyDevice::new(vec![]).first_repeat().is_err());
+    }
+
+    #[test]
+    fn first_repeat_in_reads_endless_stream() {
+        let feed = [1, 2, -2].iter().cloned().cycle();
+
+        assert_eq!(Some(1), first_repeat_in(feed));
+    }
+
+    #[test]
+    fn device_can_be_collected() {
+        let device: FrequencyDevice = vec![1, 2, 3].into_iter().collect();
+
+        assert_eq!(&[1, 2, 3], device.changes());
+    }
+}
@@ -1,77 +1,234 @@
-use std::collections::HashSet;
-use std::error::Error;
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-use std::io::BufReader;
-
-pub fn read_input(filename: &str) -> Result<Vec<i32>, io::Error> {
-    let f = File::open(filename)?;
-    let reader = BufReader::new(&f);
-    let mut result: Vec<i32> = vec![];
-
-    for line in reader.lines() {
-        let line = line.unwrap();
-
-        if line.len() > 0 {
-            let value: i32 = line.parse().unwrap();
-            result.push(value);
-        }
-    }
-
-    Ok(result)
-}
-
-pub fn calculate_frequency(filename: &str) -> Result<i32, Box<dyn Error>> {
-    let frequencies = read_input(filename)?;
-    let sum = frequencies.iter().sum();
-
-    Ok(sum)
-}
-
-pub fn find_first_duplicate_frequency(filename: &str) -> Result<i32, Box<dyn Error>> {
-    let frequencies = read_input(filename)?;
-    let mut past_frequences = HashSet::new();
-    let mut current_frequency = 0;
-    const MAX_ITERS: i32 = 1000;
-
-    for _ in 1..MAX_ITERS {
-        for freq in frequencies.iter() {
-            current_frequency += *freq;
-
-            if past_frequences.contains(&current_frequency) {
-                return Ok(current_frequency);
-            } else {
-                past_frequences.insert(current_frequency);
-            }
-        }
-    }
-
-    panic!(format!(
-        "Could not find a repeat in {} iterations",
-        MAX_ITERS
-    ));
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn read_input_handles_file() {
-        let results = read_input("inputs\\day01.txt").unwrap();
-        assert_eq!(963, results.len());
-    }
-
-    #[test]
-    fn calculate_frequency_gets_correct_result() {
-        let frequency = calculate_frequency("inputs\\day01.txt").unwrap();
-        assert_eq!(411, frequency);
-    }
-
-    #[test]
-    fn find_first_duplicate_frequency_gets_it_right() {
-        let dupe = find_first_duplicate_frequency("inputs\\day01.txt").unwrap();
-        assert_eq!(56360, dupe);
-    }
-}
+mod device;
+mod repeat;
+
+pub use self::device::{first_repeat_in, running_sum, total, FrequencyDevice, RunningSum};
+pub use self::repeat::{find_repeat, find_repeats, Position, Repeat};
+
+use std::io::BufRead;
+use std::path::Path;
+
+use error::{Error, Result};
+use input;
+use solver::Solver;
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Input = FrequencyDevice;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Chronal Calibration";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(FrequencyDevice::new(parse_input(input)?))
+    }
+
+    fn part1(&self, device: &Self::Input) -> Result<Self::Part1> {
+        device.final_frequency()
+    }
+
+    fn part2(&self, device: &Self::Input) -> Result<Self::Part2> {
+        device.first_repeat()
+    }
+}
+
+pub fn read_input<P: AsRef<Path>>(filename: P) -> Result<Vec<i64>> {
+    input::parse_file(filename, read_from)
+}
+
+/// Reads frequency changes from anything buffered, like an open file or stdin
+pub fn read_from<R: BufRead>(reader: R) -> Result<Vec<i64>> {
+    parse_input(&input::read_from(reader)?)
+}
+
+/// Reads frequency changes like `+1` or `-2`, one per line or separated by
+/// commas as in the puzzle's examples (`+1, -2, +3`). Blank lines and spaces
+/// around each change are ignored.
+pub fn parse_input(input: &str) -> Result<Vec<i64>> {
+    let mut result: Vec<i64> = vec![];
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut column = 1;
+        for token in line.split(',') {
+            result.push(parse_change(token, index + 1, column)?);
+            column += token.len() + 1;
+        }
+    }
+
+    Ok(result)
+}
+
+fn parse_change(token: &str, line: usize, column: usize) -> Result<i64> {
+    let column = column + token.len() - token.trim_start().len();
+    let token = token.trim();
+
+    if token.is_empty() {
+        return Err(Error::parse(line, column, "Missing a frequency change"));
+    }
+
+    token.parse().map_err(|err| {
+        Error::parse(
+            line,
+            column,
+            format!("Can't parse '{}' as a frequency change: {}", token, err),
+        )
+    })
+}
+
+pub fn calculate_frequency(frequencies: &[i64]) -> Result<i64> {
+    total(frequencies)
+}
+
+/// The first frequency reached twice, or an error if no frequency ever
+/// repeats
+pub fn find_first_duplicate_frequency(frequencies: &[i64]) -> Result<i64> {
+    find_first_duplicate(frequencies).map(|repeat| repeat.frequency)
+}
+
+/// Where the first frequency reached twice came from: which pass and change
+/// reached it again, and which one reached it first
+pub fn find_first_duplicate(frequencies: &[i64]) -> Result<Repeat> {
+    find_repeat(frequencies)?.ok_or_else(|| Error::domain("No frequency is ever reached twice"))
+}
+
+/// The first `count` times a frequency is reached again, in order. An error
+/// if no frequency ever repeats.
+pub fn find_duplicates(frequencies: &[i64], count: usize) -> Result<Vec<Repeat>> {
+    let repeats = find_repeats(frequencies, count)?;
+
+    if repeats.is_empty() && count > 0 {
+        return Err(Error::domain("No frequency is ever reached twice"));
+    }
+
+    Ok(repeats)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use input;
+
+    #[test]
+    fn read_input_handles_file() {
+        let results = read_input(input::path(1)).unwrap();
+        assert_eq!(963, results.len());
+    }
+
+    #[test]
+    fn read_from_handles_reader() {
+        let results = read_from("+1\n-2\n+3\n+1\n".as_bytes()).unwrap();
+        assert_eq!(vec![1, -2, 3, 1], results);
+    }
+
+    #[test]
+    fn calculate_frequency_sample() {
+        assert_eq!(3, calculate_frequency(&[1, -2, 3, 1]).unwrap());
+        assert_eq!(0, calculate_frequency(&[1, 1, -2]).unwrap());
+        assert_eq!(-6, calculate_frequency(&[-1, -2, -3]).unwrap());
+    }
+
+    #[test]
+    fn calculate_frequency_gets_correct_result() {
+        let frequencies = read_input(input::path(1)).unwrap();
+        assert_eq!(411, calculate_frequency(&frequencies).unwrap());
+    }
+
+    #[test]
+    fn find_first_duplicate_frequency_sample() {
+        let dupe = find_first_duplicate_frequency(&[3, 3, 4, -2, -4]).unwrap();
+        assert_eq!(10, dupe);
+    }
+
+    #[test]
+    fn find_first_duplicate_frequency_gets_it_right() {
+        let frequencies = read_input(input::path(1)).unwrap();
+        let dupe = find_first_duplicate_frequency(&frequencies).unwrap();
+        assert_eq!(56360, dupe);
+    }
+
+    #[test]
+    fn find_first_duplicate_reports_where() {
+        let repeat = find_first_duplicate(&[1, -2, 3, 1]).unwrap();
+
+        assert_eq!(Position { pass: 1, index: 1 }, repeat.at);
+        assert_eq!(Some(Position { pass: 0, index: 2 }), repeat.first_seen);
+    }
+
+    #[test]
+    fn find_duplicates_lists_first_few() {
+        let repeats = find_duplicates(&[3, 3, 4, -2, -4], 3).unwrap();
+        let frequencies: Vec<i64> = repeats.iter().map(|r| r.frequency).collect();
+
+        assert_eq!(10, frequencies[0]);
+        assert_eq!(3, frequencies.len());
+        assert!(find_duplicates(&[1], 3).is_err());
+    }
+
+    #[test]
+    fn parse_input_accepts_comma_separated_changes() {
+        assert_eq!(vec![1, -2, 3], parse_input("+1, -2, +3").unwrap());
+        assert_eq!(
+            vec![1, -2, 3, 1],
+            parse_input(" +1,-2\n\n  +3 \n+1\n").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_input_accepts_changes_beyond_i32() {
+        let changes = parse_input("+3000000000\n+3000000000\n").unwrap();
+        assert_eq!(6_000_000_000, calculate_frequency(&changes).unwrap());
+    }
+
+    #[test]
+    fn parse_input_reports_column_in_list() {
+        let err = parse_input("+1\n+1, -2, 3x\n").unwrap_err();
+        assert_eq!(
+            "line 2, column 9: Can't parse '3x' as a frequency change: invalid digit found in string",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn parse_input_rejects_missing_change() {
+        let err = parse_input("+1, , -2\n").unwrap_err();
+        assert_eq!(
+            "line 1, column 5: Missing a frequency change",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn parse_input_reports_bad_line() {
+        let err = parse_input("+1\n-2\nthree\n").unwrap_err();
+        assert_eq!(
+            "line 3, column 1: Can't parse 'three' as a frequency change: invalid digit found in string",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn overflowing_frequency_is_an_error() {
+        let device = Day01.parse("+9223372036854775807\n+1\n").unwrap();
+
+        assert_eq!(
+            "frequency overflows i64",
+            Day01.part1(&device).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "frequency overflows i64",
+            Day01.part2(&device).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn find_first_duplicate_frequency_without_repeat_is_an_error() {
+        let err = find_first_duplicate_frequency(&[1]).unwrap_err();
+        assert_eq!("No frequency is ever reached twice", err.to_string());
+    }
+}
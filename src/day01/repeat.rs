@@ -0,0 +1,493 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::convert::TryFrom;
+use std::fmt;
+
+use error::{Error, Result};
+
+/// A point in applying the changes over and over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    /// Which pass over the list of changes, counting from 0
+    pub pass: usize,
+
+    /// The index in the list of the change just applied
+    pub index: usize,
+}
+
+/// A time the device reaches a frequency it has been at before
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    /// The frequency that was reached again
+    pub frequency: i64,
+
+    /// The change that reached it again
+    pub at: Position,
+
+    /// The change that first reached it, or `None` if it's the starting
+    /// frequency
+    pub first_seen: Option<Position>,
+}
+
+impl fmt::Display for Repeat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} reached again on pass {} by change {}",
+            self.frequency,
+            self.at.pass + 1,
+            self.at.index + 1
+        )?;
+
+        match self.first_seen {
+            Some(first) => write!(
+                f,
+                ", first reached on pass {} by change {}",
+                first.pass + 1,
+                first.index + 1
+            ),
+            None => f.write_str(", the starting frequency"),
+        }
+    }
+}
+
+/// Finds the first frequency reached twice when the changes are applied over
+/// and over, starting from 0, or proves that no frequency ever repeats. An
+/// error if the frequency overflows an `i64` first.
+pub fn find_repeat(changes: &[i64]) -> Result<Option<Repeat>> {
+    Ok(find_repeats(changes, 1)?.pop())
+}
+
+/// Lists the first `count` times a frequency is reached again, in the order
+/// they happen. There are fewer only if frequencies never repeat at all.
+///
+/// Over one pass the frequency drifts by the sum of the changes, `D`. Every
+/// later pass visits the same frequencies as the first, each shifted by `D`
+/// again. So a frequency `f` in the first pass can only come back as an
+/// earlier frequency `g` if `g - f` is a multiple of `D` in the direction of
+/// the drift, which takes `(g - f) / D` passes. Grouping the first pass's
+/// frequencies by their remainder mod `D` and sorting each group means only
+/// neighbours in a group need to be checked, which keeps this
+/// O((n + count) log n) however many passes the repeats take.
+///
+/// An error if a frequency on the way to a repeat doesn't fit in an `i64`.
+pub fn find_repeats(changes: &[i64], count: usize) -> Result<Vec<Repeat>> {
+    let pass = FirstPass::new(changes)?;
+    let n = pass.len() as u128;
+
+    // once a position lands on a frequency seen before, it does so again on
+    // every later pass, so only its next step needs to be queued
+    let mut queue: BinaryHeap<Reverse<(u128, usize)>> = (0..pass.len())
+        .filter_map(|position| {
+            let passes = pass.passes_until_repeat(position)?;
+            Some(Reverse((passes * n + position as u128, position)))
+        })
+        .collect();
+
+    let mut repeats = vec![];
+
+    while repeats.len() < count {
+        let Reverse((step, position)) = match queue.pop() {
+            Some(next) => next,
+            None => break,
+        };
+
+        let frequency = pass.frequency_at(step)?;
+        let first_seen = match pass.first_visit(frequency) {
+            Some(0) | None => None,
+            Some(first) => Some(pass.position_of(first)),
+        };
+
+        repeats.push(Repeat {
+            frequency,
+            at: pass.position_of(step),
+            first_seen,
+        });
+
+        queue.push(Reverse((step + n, position)));
+    }
+
+    Ok(repeats)
+}
+
+/// The frequencies visited over the first pass, which determine every later
+/// one.
+///
+/// Steps count the changes applied so far, so step 0 is the starting
+/// frequency and step `k * n + j` is position `j` of pass `k`. Position 0 of
+/// a pass is also the end of the pass before it. Arithmetic between
+/// frequencies is done in `i128`, where it can't overflow.
+struct FirstPass {
+    /// The frequency at each position of the first pass
+    visited: Vec<i64>,
+
+    drift: i64,
+
+    /// Frequencies multiplied by this drift upwards, so only one direction
+    /// needs handling
+    sign: i64,
+
+    /// Positions grouped by their frequency mod the drift, sorted by
+    /// frequency with the latest position first among equals. Only used when
+    /// there is a drift.
+    groups: HashMap<i128, Vec<usize>>,
+
+    /// The first position of each frequency. Only used without a drift.
+    first_positions: HashMap<i64, usize>,
+}
+
+impl FirstPass {
+    fn new(changes: &[i64]) -> Result<FirstPass> {
+        let mut visited = Vec::with_capacity(changes.len());
+        let mut frequency = 0i64;
+
+        for change in changes.iter() {
+            visited.push(frequency);
+            frequency = frequency.checked_add(*change).ok_or_else(overflow)?;
+        }
+
+        let drift = frequency;
+        let sign = if drift < 0 { -1 } else { 1 };
+        let mut groups: HashMap<i128, Vec<usize>> = HashMap::new();
+        let mut first_positions = HashMap::new();
+
+        if drift == 0 {
+            for (position, frequency) in visited.iter().enumerate() {
+                first_positions.entry(*frequency).or_insert(position);
+            }
+        } else {
+            for (position, frequency) in visited.iter().enumerate() {
+                match groups.entry(i128::from(*frequency).rem_euclid(i128::from(drift).abs())) {
+                    Entry::Occupied(mut group) => group.get_mut().push(position),
+                    Entry::Vacant(group) => {
+                        group.insert(vec![position]);
+                    }
+                }
+            }
+
+            for group in groups.values_mut() {
+                group.sort_by_key(|&position| {
+                    (
+                        i128::from(visited[position]) * i128::from(sign),
+                        Reverse(position),
+                    )
+                });
+            }
+        }
+
+        Ok(FirstPass {
+            visited,
+            drift,
+            sign,
+            groups,
+            first_positions,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.visited.len()
+    }
+
+    /// The frequency a step reaches, or an error if it doesn't fit in an
+    /// `i64`
+    fn frequency_at(&self, step: u128) -> Result<i64> {
+        let n = self.len() as u128;
+        let passes = i128::try_from(step / n).map_err(|_| overflow())?;
+
+        passes
+            .checked_mul(i128::from(self.drift))
+            .and_then(|drifted| drifted.checked_add(i128::from(self.visited[(step % n) as usize])))
+            .and_then(|frequency| i64::try_from(frequency).ok())
+            .ok_or_else(overflow)
+    }
+
+    /// A frequency turned so the drift runs upwards
+    fn upwards(&self, frequency: i64) -> i128 {
+        i128::from(frequency) * i128::from(self.sign)
+    }
+
+    fn drift_size(&self) -> i128 {
+        i128::from(self.drift).abs()
+    }
+
+    /// The change that brought the device to a step after the start
+    fn position_of(&self, step: u128) -> Position {
+        let n = self.len() as u128;
+
+        Position {
+            pass: ((step - 1) / n) as usize,
+            index: ((step - 1) % n) as usize,
+        }
+    }
+
+    /// The step at which a frequency is first reached, if ever
+    fn first_visit(&self, frequency: i64) -> Option<u128> {
+        if self.drift == 0 {
+            return self.first_positions.get(&frequency).map(|p| *p as u128);
+        }
+
+        // the nearest frequency of the first pass behind this one drifts onto
+        // it soonest
+        let group = self
+            .groups
+            .get(&i128::from(frequency).rem_euclid(self.drift_size()))?;
+        let target = self.upwards(frequency);
+        let behind = group.partition_point(|&p| self.upwards(self.visited[p]) <= target);
+        let position = group[behind.checked_sub(1)?];
+
+        let passes = (target - self.upwards(self.visited[position])) / self.drift_size();
+        Some(passes as u128 * self.len() as u128 + position as u128)
+    }
+
+    /// How many passes until a position first lands on a frequency that was
+    /// already reached, if it ever does
+    fn passes_until_repeat(&self, position: usize) -> Option<u128> {
+        let frequency = self.visited[position];
+
+        if self.first_visit(frequency)? < position as u128 {
+            return Some(0);
+        }
+
+        if self.drift == 0 {
+            // the next pass comes back around to the same frequency
+            return Some(1);
+        }
+
+        // otherwise it has to drift onto the nearest frequency ahead of it
+        let group = self
+            .groups
+            .get(&i128::from(frequency).rem_euclid(self.drift_size()))?;
+        let target = self.upwards(frequency);
+        let ahead = group.partition_point(|&p| self.upwards(self.visited[p]) <= target);
+        let next = *group.get(ahead)?;
+
+        Some(((self.upwards(self.visited[next]) - target) / self.drift_size()) as u128)
+    }
+}
+
+fn overflow() -> Error {
+    Error::domain("frequency overflows i64")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_util::XorShift;
+
+    fn at(pass: usize, index: usize) -> Position {
+        Position { pass, index }
+    }
+
+    /// Applies the changes pass after pass, for checking against
+    fn brute_force(changes: &[i64], count: usize, max_passes: usize) -> Vec<Repeat> {
+        let mut first_seen = HashMap::new();
+        let mut repeats = vec![];
+        let mut frequency = 0i64;
+        first_seen.insert(frequency, None);
+
+        for pass in 0..max_passes {
+            for (index, change) in changes.iter().enumerate() {
+                frequency += *change;
+
+                if let Some(first) = first_seen.get(&frequency) {
+                    repeats.push(Repeat {
+                        frequency,
+                        at: at(pass, index),
+                        first_seen: *first,
+                    });
+                } else {
+                    first_seen.insert(frequency, Some(at(pass, index)));
+                }
+
+                if repeats.len() == count {
+                    return repeats;
+                }
+            }
+        }
+
+        repeats
+    }
+
+    #[test]
+    fn repeat_in_first_pass() {
+        assert_eq!(
+            Repeat {
+                frequency: 2,
+                at: at(0, 5),
+                first_seen: Some(at(0, 2)),
+            },
+            find_repeat(&[1, -2, 3, 1, 1, -2]).unwrap().unwrap()
+        );
+    }
+
+    #[test]
+    fn repeat_in_later_pass() {
+        assert_eq!(
+            Repeat {
+                frequency: 2,
+                at: at(1, 1),
+                first_seen: Some(at(0, 2)),
+            },
+            find_repeat(&[1, -2, 3, 1]).unwrap().unwrap()
+        );
+    }
+
+    #[test]
+    fn repeat_of_starting_frequency() {
+        assert_eq!(
+            Repeat {
+                frequency: 0,
+                at: at(0, 1),
+                first_seen: None,
+            },
+            find_repeat(&[1, -1]).unwrap().unwrap()
+        );
+        assert_eq!(
+            Repeat {
+                frequency: 0,
+                at: at(0, 2),
+                first_seen: None,
+            },
+            find_repeat(&[2, -1, -1]).unwrap().unwrap()
+        );
+    }
+
+    #[test]
+    fn samples() {
+        let frequency = |changes: &[i64]| find_repeat(changes).unwrap().unwrap().frequency;
+
+        assert_eq!(0, frequency(&[1, -1]));
+        assert_eq!(10, frequency(&[3, 3, 4, -2, -4]));
+        assert_eq!(5, frequency(&[-6, 3, 8, 5, -6]));
+        assert_eq!(14, frequency(&[7, 7, -2, -7, -4]));
+    }
+
+    #[test]
+    fn no_repeat_when_nothing_lines_up() {
+        assert_eq!(None, find_repeat(&[]).unwrap());
+        assert_eq!(None, find_repeat(&[1]).unwrap());
+        assert_eq!(None, find_repeat(&[3, 1]).unwrap());
+        assert!(find_repeats(&[3, 1], 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn repeat_across_the_whole_i64_range() {
+        // the frequency after the first change drifts down by 1 each pass
+        // until it reaches the 0 the device started at
+        let repeat = find_repeat(&[i64::MAX, i64::MIN]).unwrap().unwrap();
+
+        assert_eq!(
+            Repeat {
+                frequency: 0,
+                at: at(i64::MAX as usize, 0),
+                first_seen: None,
+            },
+            repeat
+        );
+    }
+
+    #[test]
+    fn overflowing_frequency_is_an_error() {
+        let changes = [i64::MAX - 2, 1, 2 - i64::MAX];
+        let frequencies = |count| {
+            find_repeats(&changes, count).map(|repeats| {
+                repeats
+                    .iter()
+                    .map(|repeat| repeat.frequency)
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert_eq!(vec![i64::MAX - 1, i64::MAX], frequencies(2).unwrap());
+        assert_eq!(
+            "frequency overflows i64",
+            frequencies(3).unwrap_err().to_string()
+        );
+        assert!(find_repeat(&[i64::MAX, 1]).is_err());
+    }
+
+    #[test]
+    fn repeat_after_many_passes() {
+        // the end of each pass drifts up by 1 until it reaches the million
+        // seen at the start
+        let repeat = find_repeat(&[1_000_000, -999_999]).unwrap().unwrap();
+
+        assert_eq!(
+            Repeat {
+                frequency: 1_000_000,
+                at: at(999_999, 1),
+                first_seen: Some(at(0, 0)),
+            },
+            repeat
+        );
+    }
+
+    #[test]
+    fn find_repeats_lists_them_in_order() {
+        let repeats = find_repeats(&[1, -2, 3, 1], 3).unwrap();
+
+        assert_eq!(
+            vec![
+                Repeat {
+                    frequency: 2,
+                    at: at(1, 1),
+                    first_seen: Some(at(0, 2)),
+                },
+                Repeat {
+                    frequency: 5,
+                    at: at(2, 1),
+                    first_seen: Some(at(1, 2)),
+                },
+                Repeat {
+                    frequency: 8,
+                    at: at(3, 1),
+                    first_seen: Some(at(2, 2)),
+                },
+            ],
+            repeats
+        );
+    }
+
+    #[test]
+    fn display_explains_repeat() {
+        let repeat = find_repeat(&[1, -2, 3, 1]).unwrap().unwrap();
+        let start = find_repeat(&[1, -1]).unwrap().unwrap();
+
+        assert_eq!(
+            "2 reached again on pass 2 by change 2, first reached on pass 1 by change 3",
+            repeat.to_string()
+        );
+        assert_eq!(
+            "0 reached again on pass 1 by change 2, the starting frequency",
+            start.to_string()
+        );
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = XorShift::new(0x2545_f491);
+
+        for _ in 0..500 {
+            let len = rng.between(1, 9) as usize;
+            let changes: Vec<i64> = (0..len).map(|_| i64::from(rng.between(-6, 6))).collect();
+
+            let expected = brute_force(&changes, 10, 200);
+            let actual = find_repeats(&changes, 10).unwrap();
+
+            // anything the brute force found comes first, and anything else
+            // only after it gave up
+            assert_eq!(
+                &expected[..],
+                &actual[..expected.len()],
+                "changes {:?}",
+                changes
+            );
+            assert!(
+                actual[expected.len()..].iter().all(|r| r.at.pass >= 200),
+                "changes {:?}",
+                changes
+            );
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::histogram::LetterHistogram;
+
+/// Every letter a box id can be made of, in order
+pub const ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// Where a letter comes in `ALPHABET`, or `None` if it isn't in it
+pub fn alphabet_index(letter: char) -> Option<usize> {
+    ALPHABET.chars().position(|other| other == letter)
+}
+
+/// The id on a box in the warehouse, made of the letters `a` to `z`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoxId {
+    id: String,
+    histogram: LetterHistogram,
+}
+
+impl BoxId {
+    /// Wraps any string as an id without checking it. Parse the id instead
+    /// to make sure it only has letters from `a` to `z`.
+    pub fn new(id: String) -> BoxId {
+        let histogram = LetterHistogram::new(&id);
+        BoxId { id, histogram }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.id
+    }
+
+    /// The number of letters in the id
+    pub fn len(&self) -> usize {
+        self.id.chars().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.id.is_empty()
+    }
+
+    /// How many times each letter appears in the id
+    pub fn histogram(&self) -> &LetterHistogram {
+        &self.histogram
+    }
+
+    /// Checks if the id has a character with exactly two instances
+    pub fn has_exactly_two(&self) -> bool {
+        self.histogram.has_count(2)
+    }
+
+    /// Checks if the id has a character with exactly three instances
+    pub fn has_exactly_three(&self) -> bool {
+        self.histogram.has_count(3)
+    }
+
+    /// Counts how many characters differ between two ids.
+    ///
+    /// If the ids are different lengths, every character past the end of
+    /// the shorter id counts as a difference
+    pub fn count_differences_against(&self, other: &BoxId) -> usize {
+        let (len, other_len) = (self.id.chars().count(), other.id.chars().count());
+        let mismatched = self
+            .id
+            .chars()
+            .zip(other.id.chars())
+            .filter(|(left, right)| left != right)
+            .count();
+
+        mismatched + len.abs_diff(other_len)
+    }
+
+    /// Counts how many characters differ position by position, or `None` if
+    /// the ids are different lengths
+    pub fn hamming_distance(&self, other: &BoxId) -> Option<usize> {
+        if self.id.chars().count() == other.id.chars().count() {
+            Some(self.count_differences_against(other))
+        } else {
+            None
+        }
+    }
+
+    /// The fewest characters to insert, remove or replace to turn one id into
+    /// the other
+    pub fn levenshtein_distance(&self, other: &BoxId) -> usize {
+        let other: Vec<char> = other.id.chars().collect();
+
+        // the distance from the start of this id seen so far to each start
+        // of the other
+        let mut previous: Vec<usize> = (0..=other.len()).collect();
+
+        for (i, letter) in self.id.chars().enumerate() {
+            let mut current = Vec::with_capacity(previous.len());
+            current.push(i + 1);
+
+            for (j, other_letter) in other.iter().enumerate() {
+                let replace = previous[j] + if letter == *other_letter { 0 } else { 1 };
+                let remove = previous[j + 1] + 1;
+                let insert = current[j] + 1;
+
+                current.push(replace.min(remove).min(insert));
+            }
+
+            previous = current;
+        }
+
+        previous[other.len()]
+    }
+
+    /// Gets a string of all the letters in common between two ids
+    pub fn common_letters_with(&self, other: &BoxId) -> String {
+        let common_chars = self
+            .id
+            .chars()
+            .zip(other.id.chars())
+            .filter(|(left, right)| left == right)
+            .map(|(left, _)| left);
+
+        let mut result = String::new();
+        for c in common_chars {
+            result.push(c);
+        }
+
+        result
+    }
+}
+
+impl FromStr for BoxId {
+    type Err = ParseBoxIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseBoxIdError::Empty);
+        }
+
+        let invalid = s
+            .chars()
+            .enumerate()
+            .find(|(_, letter)| alphabet_index(*letter).is_none());
+
+        if let Some((index, letter)) = invalid {
+            return Err(ParseBoxIdError::InvalidLetter {
+                letter,
+                column: index + 1,
+            });
+        }
+
+        Ok(BoxId::new(String::from(s)))
+    }
+}
+
+impl fmt::Display for BoxId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseBoxIdError {
+    Empty,
+    InvalidLetter { letter: char, column: usize },
+}
+
+impl fmt::Display for ParseBoxIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseBoxIdError::Empty => f.write_str("A box id can't be empty"),
+            ParseBoxIdError::InvalidLetter { letter, .. } => {
+                write!(f, "{:?} isn't one of the letters {}", letter, ALPHABET)
+            }
+        }
+    }
+}
+
+impl error::Error for ParseBoxIdError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn parse_success() {
+        let boxid: BoxId = "fghij".parse().unwrap();
+
+        assert_eq!("fghij", boxid.id());
+        assert_eq!("fghij", boxid.as_str());
+        assert_eq!("fghij", boxid.to_string());
+    }
+
+    #[test]
+    fn parse_rejects_other_characters() {
+        assert_eq!(
+            Err(ParseBoxIdError::InvalidLetter {
+                letter: 'G',
+                column: 2
+            }),
+            "fGhij".parse::<BoxId>()
+        );
+        assert_eq!(Err(ParseBoxIdError::Empty), "".parse::<BoxId>());
+    }
+
+    #[test]
+    fn parse_names_the_bad_character() {
+        let error = "abc\u{e9}".parse::<BoxId>().unwrap_err();
+
+        assert_eq!(
+            "'\u{e9}' isn't one of the letters abcdefghijklmnopqrstuvwxyz",
+            error.to_string()
+        );
+        assert_eq!(Some(0), alphabet_index('a'));
+        assert_eq!(Some(25), alphabet_index('z'));
+        assert_eq!(None, alphabet_index('A'));
+    }
+
+    #[test]
+    fn ids_can_be_set_members() {
+        let ids: HashSet<BoxId> = ["abc", "abd", "abc"]
+            .iter()
+            .map(|id| id.parse().unwrap())
+            .collect();
+
+        assert_eq!(2, ids.len());
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use super::box_id::{alphabet_index, ALPHABET};
+use super::BoxId;
+
+/// How many times each letter from `a` to `z` appears in an id. Anything
+/// else in the id isn't counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LetterHistogram {
+    counts: [u32; ALPHABET.len()],
+}
+
+impl LetterHistogram {
+    pub fn new(id: &str) -> LetterHistogram {
+        let mut counts = [0; ALPHABET.len()];
+
+        for index in id.chars().filter_map(alphabet_index) {
+            counts[index] += 1;
+        }
+
+        LetterHistogram { counts }
+    }
+
+    /// How many times a letter appears, which is 0 for anything outside
+    /// `a` to `z`
+    pub fn count(&self, letter: char) -> u32 {
+        alphabet_index(letter).map_or(0, |index| self.counts[index])
+    }
+
+    /// Checks if any letter appears exactly `times` times
+    pub fn has_count(&self, times: u32) -> bool {
+        times > 0 && self.counts.contains(&times)
+    }
+}
+
+/// How many ids have a letter repeated each number of times asked for, and
+/// those tallies multiplied together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checksum {
+    pub tallies: BTreeMap<u32, usize>,
+    pub product: u64,
+}
+
+/// Tallies the ids with some letter appearing exactly each of `repeats`
+/// times. An id counts once towards each tally however many letters match.
+/// The puzzle's checksum uses repeats of 2 and 3.
+pub fn checksum_for(boxes: &[BoxId], repeats: &[u32]) -> Checksum {
+    let mut tallies: BTreeMap<u32, usize> = repeats.iter().map(|times| (*times, 0)).collect();
+
+    for boxid in boxes.iter() {
+        for (times, tally) in tallies.iter_mut() {
+            if boxid.histogram().has_count(*times) {
+                *tally += 1;
+            }
+        }
+    }
+
+    let product = tallies.values().map(|tally| *tally as u64).product();
+
+    Checksum { tallies, product }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample() -> Vec<BoxId> {
+        [
+            "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
+        ]
+        .iter()
+        .map(|s| BoxId::new(String::from(*s)))
+        .collect()
+    }
+
+    #[test]
+    fn histogram_counts_letters() {
+        let histogram = LetterHistogram::new("bababc");
+
+        assert_eq!(2, histogram.count('a'));
+        assert_eq!(3, histogram.count('b'));
+        assert_eq!(0, histogram.count('z'));
+        assert_eq!(0, histogram.count('B'));
+        assert!(histogram.has_count(1));
+        assert!(!histogram.has_count(0));
+    }
+
+    #[test]
+    fn checksum_for_sample_tallies_each_count() {
+        let checksum = checksum_for(&sample(), &[2, 3]);
+
+        assert_eq!(Some(&4), checksum.tallies.get(&2));
+        assert_eq!(Some(&3), checksum.tallies.get(&3));
+        assert_eq!(12, checksum.product);
+    }
+
+    #[test]
+    fn checksum_for_other_counts() {
+        let checksum = checksum_for(&sample(), &[4, 2, 2]);
+
+        assert_eq!(2, checksum.tallies.len());
+        assert_eq!(Some(&0), checksum.tallies.get(&4));
+        assert_eq!(0, checksum.product);
+    }
+}
@@ -1,202 +1,241 @@
-use std::collections::HashMap;
-use std::error::Error;
-use std::fs::File;
-use std::io::BufRead;
-use std::io::BufReader;
-
-pub struct BoxId {
-    pub id: String,
-}
-
-impl BoxId {
-    pub fn new(id: String) -> BoxId {
-        BoxId { id }
-    }
-
-    fn hash(&self) -> HashMap<char, i32> {
-        let mut map = HashMap::new();
-
-        for c in self.id.chars() {
-            let count = map.entry(c).or_insert(0);
-            *count += 1;
-        }
-
-        map
-    }
-
-    /// Checks if the id has a character with exactly two instances
-    pub fn has_exactly_two(&self) -> bool {
-        self.hash().values().any(|count| *count == 2)
-    }
-
-    /// Checks if the id has a character with exactly three instances
-    pub fn has_exactly_three(&self) -> bool {
-        self.hash().values().any(|count| *count == 3)
-    }
-
-    /// Counts how many characters differ between two ids.
-    ///
-    /// If the ids are different lengths, only the length of the shorter
-    /// id is considered
-    pub fn count_differences_against(&self, other: &BoxId) -> usize {
-        self.id
-            .chars()
-            .zip(other.id.chars())
-            .filter(|(left, right)| left != right)
-            .count()
-    }
-
-    /// Gets a string of all the letters in common between two ids
-    pub fn common_letters_with(&self, other: &BoxId) -> String {
-        let common_chars = self
-            .id
-            .chars()
-            .zip(other.id.chars())
-            .filter(|(left, right)| left == right)
-            .map(|(left, _)| left);
-
-        let mut result = String::new();
-        for c in common_chars {
-            result.push(c);
-        }
-
-        result
-    }
-}
-
-pub fn load_boxes(filename: &str) -> Result<Vec<BoxId>, Box<Error>> {
-    let f = File::open(filename)?;
-
-    let reader = BufReader::new(f);
-    let mut results = vec![];
-
-    for line in reader.lines() {
-        let line = line.unwrap();
-
-        if line.len() > 0 {
-            results.push(BoxId::new(line))
-        }
-    }
-
-    Ok(results)
-}
-
-pub fn checksum(ids: &Vec<BoxId>) -> i32 {
-    let mut count2 = 0;
-    let mut count3 = 0;
-
-    for id in ids.iter() {
-        if id.has_exactly_three() {
-            count3 += 1;
-        }
-
-        if id.has_exactly_two() {
-            count2 += 1;
-        }
-    }
-
-    count2 * count3
-}
-
-pub fn find_correct_pair(boxes: &Vec<BoxId>) -> Option<(&BoxId, &BoxId)> {
-    for b1 in boxes.iter() {
-        for b2 in boxes.iter() {
-            if b1.count_differences_against(&b2) == 1 {
-                return Some((b1, b2));
-            }
-        }
-    }
-
-    None
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn load_boxes_gets_the_right_count() {
-        let boxes = load_boxes("inputs\\day02.txt").unwrap();
-        assert_eq!(250, boxes.len());
-    }
-
-    #[test]
-    fn abcdef_has_exactly_two() {
-        let boxid = BoxId::new(String::from("abcdef"));
-        assert!(boxid.has_exactly_two() == false);
-    }
-
-    #[test]
-    fn bababc_has_exactly_two() {
-        let boxid = BoxId::new(String::from("bababc"));
-        assert!(boxid.has_exactly_two());
-    }
-
-    #[test]
-    fn bababc_has_exactly_three() {
-        let boxid = BoxId::new(String::from("bababc"));
-        assert!(boxid.has_exactly_three());
-    }
-
-    #[test]
-    fn checksum_for_sample_gets_12() {
-        let boxes: Vec<BoxId> = [
-            "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
-        ]
-            .iter()
-            .map(|s| BoxId::new(String::from(*s)))
-            .collect();
-
-        assert_eq!(12, checksum(&boxes));
-    }
-
-    #[test]
-    fn checksum_for_input() {
-        let boxes = load_boxes("inputs\\day02.txt").unwrap();
-        let sum = checksum(&boxes);
-        assert_eq!(5704, sum);
-    }
-
-    #[test]
-    fn diff_count_for_abcde_and_axcye_is_two() {
-        let box1 = BoxId::new(String::from("abcde"));
-        let box2 = BoxId::new(String::from("axcye"));
-        let count = box1.count_differences_against(&box2);
-
-        assert_eq!(2, count);
-    }
-
-    #[test]
-    fn find_correct_pair_for_sample() {
-        let boxes: Vec<BoxId> = [
-            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
-        ]
-            .iter()
-            .map(|s| BoxId::new(String::from(*s)))
-            .collect();
-
-        let (match1, match2) = find_correct_pair(&boxes).unwrap();
-
-        assert_eq!(String::from("fghij"), match1.id);
-        assert_eq!(String::from("fguij"), match2.id);
-    }
-
-    #[test]
-    fn common_letters_with_sample_correct() {
-        let b1 = BoxId::new(String::from("fghij"));
-        let b2 = BoxId::new(String::from("fguij"));
-        let common = b1.common_letters_with(&b2);
-
-        assert_eq!("fgij", common);
-    }
-
-    #[test]
-    fn common_letters_from_input() {
-        let boxes = load_boxes("inputs\\day02.txt").unwrap();
-        let (box1, box2) = find_correct_pair(&boxes).unwrap();
-
-        let common = box1.common_letters_with(&box2);
-
-        assert_eq!("umdryabviapkozistwcnihjqx", common);
-    }
-}
+mod box_id;
+mod histogram;
+mod pairs;
+
+pub use self::box_id::{BoxId, ParseBoxIdError, ALPHABET};
+pub use self::histogram::{checksum_for, Checksum, LetterHistogram};
+pub use self::pairs::{cluster, find_near_pairs, find_pairs_within, Distance};
+
+use std::io::BufRead;
+use std::path::Path;
+
+use error::{Error, Result};
+use input;
+use solver::Solver;
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Input = Vec<BoxId>;
+    type Part1 = u64;
+    type Part2 = String;
+
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Inventory Management System";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, boxes: &Self::Input) -> Result<Self::Part1> {
+        Ok(checksum(boxes))
+    }
+
+    fn part2(&self, boxes: &Self::Input) -> Result<Self::Part2> {
+        let (b1, b2) = find_correct_pair(boxes)
+            .ok_or_else(|| Error::domain("no two boxes differ by exactly one letter"))?;
+        Ok(b1.common_letters_with(b2))
+    }
+}
+
+pub fn load_boxes<P: AsRef<Path>>(filename: P) -> Result<Vec<BoxId>> {
+    input::parse_file(filename, read_from)
+}
+
+/// Reads box ids from anything buffered, like an open file or stdin
+pub fn read_from<R: BufRead>(reader: R) -> Result<Vec<BoxId>> {
+    parse_input(&input::read_from(reader)?)
+}
+
+/// Reads one box id per line, skipping blank lines. Every id has to be the
+/// same length as the first.
+pub fn parse_input(input: &str) -> Result<Vec<BoxId>> {
+    let mut result: Vec<BoxId> = vec![];
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let boxid: BoxId = line.parse().map_err(|err| {
+            let column = match err {
+                ParseBoxIdError::InvalidLetter { column, .. } => column,
+                ParseBoxIdError::Empty => 1,
+            };
+
+            Error::parse(
+                index + 1,
+                column,
+                format!("Can't parse '{}' as a box id: {}", line, err),
+            )
+        })?;
+
+        if let Some(first) = result.first() {
+            if first.len() != boxid.len() {
+                return Err(Error::parse(
+                    index + 1,
+                    1,
+                    format!(
+                        "Box id '{}' has {} letters but '{}' has {}",
+                        boxid,
+                        boxid.len(),
+                        first,
+                        first.len()
+                    ),
+                ));
+            }
+        }
+
+        result.push(boxid);
+    }
+
+    Ok(result)
+}
+
+/// The number of ids with a letter appearing exactly twice times the number
+/// with a letter appearing exactly three times
+pub fn checksum(ids: &[BoxId]) -> u64 {
+    checksum_for(ids, &[2, 3]).product
+}
+
+/// The first pair of boxes, in list order, whose ids differ by exactly one
+/// letter
+pub fn find_correct_pair(boxes: &[BoxId]) -> Option<(&BoxId, &BoxId)> {
+    find_near_pairs(boxes)
+        .first()
+        .map(|&(first, second)| (&boxes[first], &boxes[second]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use input;
+
+    #[test]
+    fn load_boxes_gets_the_right_count() {
+        let boxes = load_boxes(input::path(2)).unwrap();
+        assert_eq!(250, boxes.len());
+    }
+
+    #[test]
+    fn parse_input_reports_bad_letter() {
+        let err = parse_input("abcde\nab3de\n").unwrap_err();
+
+        assert_eq!(
+            "line 2, column 3: Can't parse 'ab3de' as a box id: '3' isn't one of the letters abcdefghijklmnopqrstuvwxyz",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn parse_input_needs_same_length() {
+        let err = parse_input("abcde\n\nabcd\n").unwrap_err();
+
+        assert_eq!(
+            "line 3, column 1: Box id 'abcd' has 4 letters but 'abcde' has 5",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn abcdef_has_exactly_two() {
+        let boxid = BoxId::new(String::from("abcdef"));
+        assert!(boxid.has_exactly_two() == false);
+    }
+
+    #[test]
+    fn bababc_has_exactly_two() {
+        let boxid = BoxId::new(String::from("bababc"));
+        assert!(boxid.has_exactly_two());
+    }
+
+    #[test]
+    fn bababc_has_exactly_three() {
+        let boxid = BoxId::new(String::from("bababc"));
+        assert!(boxid.has_exactly_three());
+    }
+
+    #[test]
+    fn checksum_for_sample_gets_12() {
+        let boxes: Vec<BoxId> = [
+            "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
+        ]
+        .iter()
+        .map(|s| BoxId::new(String::from(*s)))
+        .collect();
+
+        assert_eq!(12, checksum(&boxes));
+    }
+
+    #[test]
+    fn checksum_for_input() {
+        let boxes = load_boxes(input::path(2)).unwrap();
+        let sum = checksum(&boxes);
+        assert_eq!(5704, sum);
+    }
+
+    #[test]
+    fn diff_count_for_abcde_and_axcye_is_two() {
+        let box1 = BoxId::new(String::from("abcde"));
+        let box2 = BoxId::new(String::from("axcye"));
+        let count = box1.count_differences_against(&box2);
+
+        assert_eq!(2, count);
+    }
+
+    #[test]
+    fn diff_count_includes_extra_letters() {
+        let box1 = BoxId::new(String::from("abcde"));
+        let box2 = BoxId::new(String::from("abc"));
+
+        assert_eq!(2, box1.count_differences_against(&box2));
+        assert_eq!(None, box1.hamming_distance(&box2));
+    }
+
+    #[test]
+    fn levenshtein_distance_samples() {
+        let distance = |a: &str, b: &str| {
+            BoxId::new(String::from(a)).levenshtein_distance(&BoxId::new(String::from(b)))
+        };
+
+        assert_eq!(3, distance("kitten", "sitting"));
+        assert_eq!(1, distance("fghij", "fguij"));
+        assert_eq!(4, distance("", "abcd"));
+        assert_eq!(0, distance("abc", "abc"));
+    }
+
+    #[test]
+    fn find_correct_pair_for_sample() {
+        let boxes: Vec<BoxId> = [
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ]
+        .iter()
+        .map(|s| BoxId::new(String::from(*s)))
+        .collect();
+
+        let (match1, match2) = find_correct_pair(&boxes).unwrap();
+
+        assert_eq!("fghij", match1.id());
+        assert_eq!("fguij", match2.id());
+    }
+
+    #[test]
+    fn common_letters_with_sample_correct() {
+        let b1 = BoxId::new(String::from("fghij"));
+        let b2 = BoxId::new(String::from("fguij"));
+        let common = b1.common_letters_with(&b2);
+
+        assert_eq!("fgij", common);
+    }
+
+    #[test]
+    fn common_letters_from_input() {
+        let boxes = load_boxes(input::path(2)).unwrap();
+        let (box1, box2) = find_correct_pair(&boxes).unwrap();
+
+        let common = box1.common_letters_with(&box2);
+
+        assert_eq!("umdryabviapkozistwcnihjqx", common);
+    }
+}
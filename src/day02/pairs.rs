@@ -0,0 +1,362 @@
+use std::collections::{HashMap, HashSet};
+
+use super::BoxId;
+
+/// How far apart two ids are
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distance {
+    /// Letters that differ position by position. Ids of different lengths
+    /// are never close.
+    Hamming,
+
+    /// Letters inserted, removed or replaced to turn one id into the other,
+    /// so ids of different lengths can be close
+    Levenshtein,
+}
+
+impl Distance {
+    /// The distance between two ids, or `None` if it's more than `max`
+    pub fn between(self, first: &BoxId, second: &BoxId, max: usize) -> Option<usize> {
+        let distance = match self {
+            Distance::Hamming => first.hamming_distance(second)?,
+            Distance::Levenshtein => first.levenshtein_distance(second),
+        };
+
+        if distance <= max {
+            Some(distance)
+        } else {
+            None
+        }
+    }
+}
+
+/// Finds every pair of ids that differ in exactly one position, as indexes
+/// into `boxes` with the earlier one first, in order.
+///
+/// Each id is filed under every way of blanking out one of its letters, so
+/// two ids differing only in that letter land in the same bucket. A bucket is
+/// keyed on the blanked position and hashes of the letters either side of
+/// it, which rolling hashes give in O(1), so filing `n` ids of length `L`
+/// takes O(n·L). Within a bucket ids are grouped by the letter blanked out,
+/// and only ids from different groups are paired, so identical ids cost
+/// nothing. Each pair found is checked letter by letter in case of a hash
+/// collision, which adds O(L) for every pair.
+pub fn find_near_pairs(boxes: &[BoxId]) -> Vec<(usize, usize)> {
+    let mut buckets: HashMap<(usize, u64, u64), HashMap<char, Vec<usize>>> = HashMap::new();
+
+    for (index, boxid) in boxes.iter().enumerate() {
+        let letters: Vec<char> = boxid.as_str().chars().collect();
+        let (prefixes, suffixes) = rolling_hashes(&letters);
+
+        for (at, letter) in letters.iter().enumerate() {
+            buckets
+                .entry((at, prefixes[at], suffixes[at + 1]))
+                .or_default()
+                .entry(*letter)
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let mut pairs = vec![];
+
+    for bucket in buckets.values().filter(|bucket| bucket.len() > 1) {
+        let groups: Vec<&Vec<usize>> = bucket.values().collect();
+
+        for (i, firsts) in groups.iter().enumerate() {
+            for seconds in groups[i + 1..].iter() {
+                for &first in firsts.iter() {
+                    for &second in seconds.iter() {
+                        pairs.push((first.min(second), first.max(second)));
+                    }
+                }
+            }
+        }
+    }
+
+    pairs.retain(|&(first, second)| boxes[first].hamming_distance(&boxes[second]) == Some(1));
+    pairs.sort_unstable();
+    pairs.dedup();
+    pairs
+}
+
+/// Polynomial hashes of every prefix and every suffix of some letters, so
+/// `prefixes[i]` covers the letters before `i` and `suffixes[i]` the letters
+/// from `i` on. Both have one more entry than there are letters.
+fn rolling_hashes(letters: &[char]) -> (Vec<u64>, Vec<u64>) {
+    const MODULUS: u64 = (1 << 61) - 1;
+    const BASE: u64 = 1_000_003;
+
+    let step = |hash: u64, letter: char| {
+        ((u128::from(hash) * u128::from(BASE) + u128::from(letter) + 1) % u128::from(MODULUS))
+            as u64
+    };
+
+    let mut prefixes = vec![0; letters.len() + 1];
+    for (at, letter) in letters.iter().enumerate() {
+        prefixes[at + 1] = step(prefixes[at], *letter);
+    }
+
+    let mut suffixes = vec![0; letters.len() + 1];
+    for (at, letter) in letters.iter().enumerate().rev() {
+        suffixes[at] = step(suffixes[at + 1], *letter);
+    }
+
+    (prefixes, suffixes)
+}
+
+/// Finds every pair of ids within `max` of each other, as indexes into
+/// `boxes` with the earlier one first, in order. Identical ids count as a
+/// pair, but an id is never paired with itself.
+///
+/// For Hamming distance each id is cut into `max + 1` pieces. Two ids with at
+/// most `max` differences must agree on at least one whole piece, so only ids
+/// sharing a piece are compared. For Levenshtein distance only ids whose
+/// lengths are within `max` of each other are compared.
+pub fn find_pairs_within(boxes: &[BoxId], max: usize, distance: Distance) -> Vec<(usize, usize)> {
+    let candidates = match distance {
+        Distance::Hamming => sharing_a_piece(boxes, max + 1),
+        Distance::Levenshtein => similar_lengths(boxes, max),
+    };
+
+    let mut pairs: Vec<(usize, usize)> = candidates
+        .into_iter()
+        .filter(|&(first, second)| {
+            distance
+                .between(&boxes[first], &boxes[second], max)
+                .is_some()
+        })
+        .collect();
+
+    pairs.sort();
+    pairs
+}
+
+/// Groups the ids so that any two within `max` of each other end up in the
+/// same group, along with anything linked to them through a chain of close
+/// ids. Every id is in exactly one group. Groups are ordered by their first
+/// id and hold indexes into `boxes` in order.
+pub fn cluster(boxes: &[BoxId], max: usize, distance: Distance) -> Vec<Vec<usize>> {
+    let mut leaders: Vec<usize> = (0..boxes.len()).collect();
+
+    for (first, second) in find_pairs_within(boxes, max, distance) {
+        let (a, b) = (leader(&mut leaders, first), leader(&mut leaders, second));
+        leaders[a.max(b)] = a.min(b);
+    }
+
+    let mut groups: Vec<Vec<usize>> = vec![];
+    let mut group_of_leader = HashMap::new();
+
+    for index in 0..boxes.len() {
+        let group = *group_of_leader
+            .entry(leader(&mut leaders, index))
+            .or_insert_with(|| {
+                groups.push(vec![]);
+                groups.len() - 1
+            });
+
+        groups[group].push(index);
+    }
+
+    groups
+}
+
+/// Follows the chain of leaders up to the one leading the whole group,
+/// shortening it along the way
+fn leader(leaders: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while leaders[root] != root {
+        root = leaders[root];
+    }
+
+    let mut current = index;
+    while leaders[current] != root {
+        let next = leaders[current];
+        leaders[current] = root;
+        current = next;
+    }
+
+    root
+}
+
+/// Pairs of ids with the same length that agree on at least one of `pieces`
+/// pieces
+fn sharing_a_piece(boxes: &[BoxId], pieces: usize) -> HashSet<(usize, usize)> {
+    let mut buckets: HashMap<(usize, usize, &[char]), Vec<usize>> = HashMap::new();
+    let letters: Vec<Vec<char>> = boxes.iter().map(|b| b.id().chars().collect()).collect();
+
+    for (index, id) in letters.iter().enumerate() {
+        let len = id.len();
+
+        for piece in 0..pieces {
+            let part = &id[piece * len / pieces..(piece + 1) * len / pieces];
+            buckets.entry((len, piece, part)).or_default().push(index);
+        }
+    }
+
+    let mut candidates = HashSet::new();
+    for bucket in buckets.values() {
+        for (i, &first) in bucket.iter().enumerate() {
+            for &second in bucket[i + 1..].iter() {
+                candidates.insert((first, second));
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Pairs of ids whose lengths differ by at most `max`
+fn similar_lengths(boxes: &[BoxId], max: usize) -> HashSet<(usize, usize)> {
+    let mut by_length: Vec<(usize, usize)> = boxes
+        .iter()
+        .enumerate()
+        .map(|(index, b)| (b.id().chars().count(), index))
+        .collect();
+    by_length.sort();
+
+    let mut candidates = HashSet::new();
+    for (i, &(len, first)) in by_length.iter().enumerate() {
+        for &(other_len, second) in by_length[i + 1..].iter() {
+            if other_len - len > max {
+                break;
+            }
+
+            candidates.insert((first.min(second), first.max(second)));
+        }
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_util::XorShift;
+
+    fn boxes(ids: &[&str]) -> Vec<BoxId> {
+        ids.iter().map(|id| BoxId::new(String::from(*id))).collect()
+    }
+
+    #[test]
+    fn finds_every_pair() {
+        let boxes = boxes(&["abcde", "fghij", "klmno", "abcdx", "fguij", "xbcde"]);
+
+        assert_eq!(vec![(0, 3), (0, 5), (1, 4)], find_near_pairs(&boxes));
+    }
+
+    #[test]
+    fn identical_ids_are_not_pairs() {
+        let boxes = boxes(&["abc", "abc", "abd"]);
+
+        assert_eq!(vec![(0, 2), (1, 2)], find_near_pairs(&boxes));
+    }
+
+    #[test]
+    fn many_identical_ids_only_pair_with_the_odd_one_out() {
+        let mut ids = vec!["abcdefghijklmnopqrstuvwxyz"; 5000];
+        ids.push("abcdefghijklmnopqrstuvwxyy");
+        let pairs = find_near_pairs(&boxes(&ids));
+
+        assert_eq!(5000, pairs.len());
+        assert!(pairs.iter().all(|&(_, second)| second == 5000));
+        assert!(find_near_pairs(&boxes(&ids[..5000])).is_empty());
+    }
+
+    #[test]
+    fn different_lengths_are_not_pairs() {
+        let boxes = boxes(&["abc", "ab", "abcd"]);
+
+        assert!(find_near_pairs(&boxes).is_empty());
+    }
+
+    #[test]
+    fn pairs_within_two_letters() {
+        let boxes = boxes(&["abcde", "abxye", "abcde", "vwxyz", "abxyz"]);
+
+        assert_eq!(
+            vec![(0, 1), (0, 2), (1, 2), (1, 4), (3, 4)],
+            find_pairs_within(&boxes, 2, Distance::Hamming)
+        );
+    }
+
+    #[test]
+    fn levenshtein_pairs_ids_of_different_lengths() {
+        let boxes = boxes(&["abcde", "abde", "xabcde", "abc"]);
+
+        assert_eq!(
+            vec![(0, 1), (0, 2)],
+            find_pairs_within(&boxes, 1, Distance::Levenshtein)
+        );
+        assert!(find_pairs_within(&boxes, 1, Distance::Hamming).is_empty());
+    }
+
+    #[test]
+    fn cluster_follows_chains() {
+        let boxes = boxes(&["aaaa", "zzzz", "aaab", "zzzy", "aabb", "qqqq"]);
+
+        assert_eq!(
+            vec![vec![0, 2, 4], vec![1, 3], vec![5]],
+            cluster(&boxes, 1, Distance::Hamming)
+        );
+    }
+
+    #[test]
+    fn pairs_within_match_comparing_every_pair() {
+        let mut rng = XorShift::new(0x1234_5677);
+
+        for _ in 0..30 {
+            let ids: Vec<String> = (0..30)
+                .map(|_| {
+                    let len = 3 + rng.below(3) as usize;
+                    (0..len)
+                        .map(|_| (b'a' + rng.below(3) as u8) as char)
+                        .collect()
+                })
+                .collect();
+            let boxes: Vec<BoxId> = ids.into_iter().map(BoxId::new).collect();
+
+            for &distance in [Distance::Hamming, Distance::Levenshtein].iter() {
+                for max in 0..4 {
+                    let mut expected = vec![];
+                    for i in 0..boxes.len() {
+                        for j in i + 1..boxes.len() {
+                            if distance.between(&boxes[i], &boxes[j], max).is_some() {
+                                expected.push((i, j));
+                            }
+                        }
+                    }
+
+                    assert_eq!(expected, find_pairs_within(&boxes, max, distance));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matches_comparing_every_pair() {
+        let mut rng = XorShift::new(0x9e37_79b9);
+
+        for _ in 0..50 {
+            let ids: Vec<String> = (0..40)
+                .map(|_| {
+                    (0..4)
+                        .map(|_| (b'a' + rng.below(3) as u8) as char)
+                        .collect()
+                })
+                .collect();
+            let boxes: Vec<BoxId> = ids.into_iter().map(BoxId::new).collect();
+
+            let mut expected = vec![];
+            for i in 0..boxes.len() {
+                for j in i + 1..boxes.len() {
+                    if boxes[i].count_differences_against(&boxes[j]) == 1 {
+                        expected.push((i, j));
+                    }
+                }
+            }
+
+            assert_eq!(expected, find_near_pairs(&boxes));
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use geometry::{Point, Rect};
+
+use super::sweep::{coverage, overlapping_pairs};
+use super::FabricClaim;
+
+/// Everything about how a set of claims overlap, worked out once
+#[derive(Debug, Clone)]
+pub struct FabricAnalysis<'a> {
+    claims: &'a [FabricClaim],
+
+    /// The indexes of the claims overlapping each claim, by index, so claims
+    /// sharing an id are still told apart
+    overlapping: Vec<BTreeSet<usize>>,
+
+    /// The ids each claim's id overlaps, with an entry for every claim.
+    /// Claims sharing an id share an entry.
+    conflicts: BTreeMap<i32, BTreeSet<i32>>,
+
+    /// The area shared by each overlapping pair of ids, smaller id first
+    overlaps: BTreeMap<(i32, i32), u64>,
+
+    /// Rectangles covering all the claimed fabric without overlapping each
+    /// other, with how many claims cover each
+    coverage: Vec<(Rect, u32)>,
+}
+
+impl<'a> FabricAnalysis<'a> {
+    pub fn new(claims: &'a [FabricClaim]) -> FabricAnalysis<'a> {
+        let mut conflicts: BTreeMap<i32, BTreeSet<i32>> = claims
+            .iter()
+            .map(|claim| (claim.id, BTreeSet::new()))
+            .collect();
+        let mut overlapping = vec![BTreeSet::new(); claims.len()];
+        let mut overlaps = BTreeMap::new();
+
+        for (i, j) in overlapping_pairs(claims) {
+            overlapping[i].insert(j);
+            overlapping[j].insert(i);
+
+            let (first, second) = (&claims[i], &claims[j]);
+            let area = first
+                .rect
+                .intersection(&second.rect)
+                .map_or(0, |shared| shared.area());
+
+            conflicts.entry(first.id).or_default().insert(second.id);
+            conflicts.entry(second.id).or_default().insert(first.id);
+
+            let key = (first.id.min(second.id), first.id.max(second.id));
+            *overlaps.entry(key).or_insert(0) += area;
+        }
+
+        FabricAnalysis {
+            claims,
+            overlapping,
+            conflicts,
+            overlaps,
+            coverage: coverage(claims),
+        }
+    }
+
+    pub fn claims(&self) -> &'a [FabricClaim] {
+        self.claims
+    }
+
+    /// Every claim that doesn't overlap any other, in the order given
+    pub fn non_overlapping(&self) -> Vec<&'a FabricClaim> {
+        self.claims
+            .iter()
+            .zip(self.overlapping.iter())
+            .filter(|(_, others)| others.is_empty())
+            .map(|(claim, _)| claim)
+            .collect()
+    }
+
+    /// The indexes of the claims overlapping the claim at `index`, or `None`
+    /// if there's no claim there
+    pub fn overlapping(&self, index: usize) -> Option<&BTreeSet<usize>> {
+        self.overlapping.get(index)
+    }
+
+    /// The ids of the claims overlapping a claim, or `None` if there's no
+    /// claim with that id. Claims sharing the id are counted together.
+    pub fn conflicts_of(&self, id: i32) -> Option<&BTreeSet<i32>> {
+        self.conflicts.get(&id)
+    }
+
+    /// Every claim id with the ids it overlaps, in order of id
+    pub fn conflicts(&self) -> &BTreeMap<i32, BTreeSet<i32>> {
+        &self.conflicts
+    }
+
+    /// The area two claims share, which is 0 if they don't overlap
+    pub fn overlap_area_between(&self, first: i32, second: i32) -> u64 {
+        let key = (first.min(second), first.max(second));
+
+        self.overlaps.get(&key).cloned().unwrap_or(0)
+    }
+
+    /// The area shared by every overlapping pair of ids, keyed smaller id
+    /// first
+    pub fn overlaps(&self) -> &BTreeMap<(i32, i32), u64> {
+        &self.overlaps
+    }
+
+    /// Rectangles covering every square inch claimed at least `times` times,
+    /// without overlapping each other. A `times` of 0 is treated as 1.
+    pub fn regions_with_at_least(&self, times: u32) -> Vec<Rect> {
+        self.coverage
+            .iter()
+            .filter(|(_, count)| *count >= times)
+            .map(|(rect, _)| *rect)
+            .collect()
+    }
+
+    /// Every square inch claimed at least `times` times
+    pub fn cells_with_at_least(&self, times: u32) -> impl Iterator<Item = Point> {
+        self.regions_with_at_least(times)
+            .into_iter()
+            .flat_map(|region| region.points())
+    }
+
+    /// The area claimed at least `times` times
+    pub fn area_with_at_least(&self, times: u32) -> u64 {
+        self.regions_with_at_least(times)
+            .iter()
+            .map(Rect::area)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::sweep::regions_covered_by;
+    use super::*;
+
+    fn claims() -> Vec<FabricClaim> {
+        [
+            "#1 @ 1,3: 4x4",
+            "#2 @ 3,1: 4x4",
+            "#3 @ 5,5: 2x2",
+            "#4 @ 4,4: 2x2",
+        ]
+        .iter()
+        .map(|line| line.parse().unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn non_overlapping_finds_all() {
+        let claims: Vec<FabricClaim> = ["#1 @ 0,0: 2x2", "#2 @ 5,5: 1x1", "#3 @ 1,1: 2x2"]
+            .iter()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        let analysis = FabricAnalysis::new(&claims);
+        let ids: Vec<i32> = analysis.non_overlapping().iter().map(|c| c.id).collect();
+
+        assert_eq!(vec![2], ids);
+    }
+
+    #[test]
+    fn duplicate_ids_are_told_apart() {
+        let claims: Vec<FabricClaim> = ["#1 @ 0,0: 2x2", "#1 @ 10,10: 1x1", "#2 @ 1,1: 2x2"]
+            .iter()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        let analysis = FabricAnalysis::new(&claims);
+
+        let others = |index| {
+            analysis
+                .overlapping(index)
+                .unwrap()
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(vec![&claims[1]], analysis.non_overlapping());
+        assert_eq!(vec![2], others(0));
+        assert!(others(1).is_empty());
+        assert_eq!(None, analysis.overlapping(3));
+        assert_eq!(
+            vec![&2],
+            analysis.conflicts_of(1).unwrap().iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn conflicts_list_every_overlapping_id() {
+        let claims = claims();
+        let analysis = FabricAnalysis::new(&claims);
+
+        let ids = |id| {
+            analysis
+                .conflicts_of(id)
+                .unwrap()
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(vec![2, 4], ids(1));
+        assert_eq!(vec![1, 4], ids(2));
+        assert_eq!(vec![4], ids(3));
+        assert_eq!(vec![1, 2, 3], ids(4));
+        assert_eq!(None, analysis.conflicts_of(5));
+    }
+
+    #[test]
+    fn overlap_area_between_pairs() {
+        let claims = claims();
+        let analysis = FabricAnalysis::new(&claims);
+
+        assert_eq!(4, analysis.overlap_area_between(2, 1));
+        assert_eq!(1, analysis.overlap_area_between(3, 4));
+        assert_eq!(0, analysis.overlap_area_between(1, 3));
+        assert_eq!(4, analysis.overlaps().len());
+    }
+
+    #[test]
+    fn cells_with_at_least_three() {
+        let claims = claims();
+        let analysis = FabricAnalysis::new(&claims);
+        let mut cells: Vec<Point> = analysis.cells_with_at_least(3).collect();
+        cells.sort();
+
+        assert_eq!(vec![Point::new(4, 4)], cells);
+        assert_eq!(7, analysis.area_with_at_least(2));
+    }
+
+    #[test]
+    fn regions_match_the_sweep() {
+        let claims = claims();
+        let analysis = FabricAnalysis::new(&claims);
+
+        for times in 0..5 {
+            let area = |regions: Vec<Rect>| regions.iter().map(Rect::area).sum::<u64>();
+
+            assert_eq!(
+                area(regions_covered_by(&claims, times)),
+                area(analysis.regions_with_at_least(times))
+            );
+        }
+    }
+}
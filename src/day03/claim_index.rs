@@ -0,0 +1,337 @@
+use geometry::{Point, Rect};
+
+use super::FabricClaim;
+
+/// Most entries held by one node of the tree
+const NODE_CAPACITY: usize = 8;
+
+/// An R-tree over claims, for finding the claims covering a square inch or
+/// touching an area without checking every claim.
+///
+/// The tree is packed once from all the claims: they're sorted into columns
+/// by their centre, each column sorted down by centre, and cut into nodes of
+/// neighbouring claims. The nodes are then packed the same way, level by
+/// level, up to a single root. Claims covering no fabric are left out since
+/// nothing can find them. Node bounds are kept in `i64`, since a node can
+/// span claims further apart than an `i32` width.
+#[derive(Debug, Clone)]
+pub struct ClaimIndex<'a> {
+    claims: &'a [FabricClaim],
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    bounds: Bounds,
+
+    /// Indexes of claims in a leaf, or of nodes in a branch
+    entries: Vec<usize>,
+    is_leaf: bool,
+}
+
+impl<'a> ClaimIndex<'a> {
+    pub fn new(claims: &'a [FabricClaim]) -> ClaimIndex<'a> {
+        let mut index = ClaimIndex {
+            claims,
+            nodes: vec![],
+            root: None,
+        };
+
+        let mut level: Vec<(Bounds, usize)> = claims
+            .iter()
+            .enumerate()
+            .filter(|(_, claim)| !claim.rect.is_empty())
+            .map(|(i, claim)| (Bounds::from(claim.rect), i))
+            .collect();
+        let mut is_leaf = true;
+
+        while !level.is_empty() {
+            let nodes = pack(level);
+            let first = index.nodes.len();
+
+            index
+                .nodes
+                .extend(nodes.into_iter().map(|(bounds, entries)| Node {
+                    bounds,
+                    entries,
+                    is_leaf,
+                }));
+
+            if index.nodes.len() - first == 1 {
+                index.root = Some(first);
+                break;
+            }
+
+            level = (first..index.nodes.len())
+                .map(|n| (index.nodes[n].bounds, n))
+                .collect();
+            is_leaf = false;
+        }
+
+        index
+    }
+
+    /// Every claim covering a square inch, in the order given
+    pub fn claims_at(&self, point: Point) -> Vec<&'a FabricClaim> {
+        self.claims_matching(|bounds| bounds.contains(point))
+    }
+
+    /// Every claim sharing at least one square inch with an area, in the
+    /// order given
+    pub fn claims_in(&self, area: Rect) -> Vec<&'a FabricClaim> {
+        let area = Bounds::from(area);
+
+        self.claims_matching(|bounds| bounds.intersects(&area))
+    }
+
+    /// Checks if the claim at a position in the indexed claims shares any
+    /// square inch with another claim
+    pub fn overlaps_another(&self, index: usize) -> bool {
+        let area = Bounds::from(self.claims[index].rect);
+
+        !self
+            .search(|bounds| bounds.intersects(&area), Some(index))
+            .is_empty()
+    }
+
+    fn claims_matching<F: Fn(&Bounds) -> bool>(&self, matches: F) -> Vec<&'a FabricClaim> {
+        self.search(matches, None)
+            .into_iter()
+            .map(|i| &self.claims[i])
+            .collect()
+    }
+
+    /// Finds the positions of the claims whose bounds match, leaving out
+    /// `except`. Nodes whose bounds don't match can't hold any that do.
+    fn search<F: Fn(&Bounds) -> bool>(&self, matches: F, except: Option<usize>) -> Vec<usize> {
+        let mut found: Vec<usize> = vec![];
+        let mut pending: Vec<usize> = self.root.into_iter().collect();
+
+        while let Some(n) = pending.pop() {
+            let node = &self.nodes[n];
+            if !matches(&node.bounds) {
+                continue;
+            }
+
+            if node.is_leaf {
+                found.extend(node.entries.iter().filter(|&&i| {
+                    Some(i) != except && matches(&Bounds::from(self.claims[i].rect))
+                }));
+            } else {
+                pending.extend(node.entries.iter());
+            }
+        }
+
+        found.sort_unstable();
+        found
+    }
+}
+
+/// A rectangle covering `left..right` and `top..bottom`, wide enough to
+/// hold any set of `i32` rectangles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Bounds {
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+}
+
+impl Bounds {
+    /// The smallest bounds covering both. Empty bounds add nothing.
+    fn union(&self, other: &Bounds) -> Bounds {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+
+        Bounds {
+            left: self.left.min(other.left),
+            top: self.top.min(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.max(other.bottom),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.left >= self.right || self.top >= self.bottom
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        let (x, y) = (i64::from(point.x), i64::from(point.y));
+
+        x >= self.left && x < self.right && y >= self.top && y < self.bottom
+    }
+
+    fn intersects(&self, other: &Bounds) -> bool {
+        self.left.max(other.left) < self.right.min(other.right)
+            && self.top.max(other.top) < self.bottom.min(other.bottom)
+    }
+
+    /// Twice the centre, so it stays a whole number
+    fn centre(&self) -> (i64, i64) {
+        (self.left + self.right, self.top + self.bottom)
+    }
+}
+
+impl From<Rect> for Bounds {
+    fn from(rect: Rect) -> Bounds {
+        let (left, top) = (i64::from(rect.left), i64::from(rect.top));
+
+        Bounds {
+            left,
+            top,
+            right: left + i64::from(rect.width),
+            bottom: top + i64::from(rect.height),
+        }
+    }
+}
+
+/// Groups entries into nodes of up to `NODE_CAPACITY` close neighbours,
+/// giving the bounds and entries of each
+fn pack(mut entries: Vec<(Bounds, usize)>) -> Vec<(Bounds, Vec<usize>)> {
+    let node_count = entries.len().div_ceil(NODE_CAPACITY);
+    let columns = (node_count as f64).sqrt().ceil() as usize;
+    let column_size = columns.max(1) * NODE_CAPACITY;
+
+    entries.sort_by_key(|(bounds, _)| bounds.centre().0);
+
+    let mut nodes = vec![];
+    for column in entries.chunks_mut(column_size) {
+        column.sort_by_key(|(bounds, _)| bounds.centre().1);
+
+        for node in column.chunks(NODE_CAPACITY) {
+            let bounds = node
+                .iter()
+                .fold(Bounds::default(), |union, (bounds, _)| union.union(bounds));
+
+            nodes.push((bounds, node.iter().map(|(_, i)| *i).collect()));
+        }
+    }
+
+    nodes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_util::XorShift;
+
+    fn sample() -> Vec<FabricClaim> {
+        ["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2"]
+            .iter()
+            .map(|line| line.parse().unwrap())
+            .collect()
+    }
+
+    fn ids(claims: Vec<&FabricClaim>) -> Vec<i32> {
+        claims.iter().map(|c| c.id).collect()
+    }
+
+    #[test]
+    fn claims_at_sample() {
+        let claims = sample();
+        let index = ClaimIndex::new(&claims);
+
+        assert_eq!(vec![1, 2], ids(index.claims_at(Point::new(3, 3))));
+        assert_eq!(vec![3], ids(index.claims_at(Point::new(6, 6))));
+        assert!(index.claims_at(Point::new(0, 0)).is_empty());
+    }
+
+    #[test]
+    fn claims_in_sample() {
+        let claims = sample();
+        let index = ClaimIndex::new(&claims);
+
+        assert_eq!(vec![1, 2, 3], ids(index.claims_in(Rect::new(4, 4, 2, 2))));
+        assert_eq!(vec![2], ids(index.claims_in(Rect::new(5, 0, 10, 2))));
+        assert!(index.claims_in(Rect::new(5, 5, 0, 9)).is_empty());
+    }
+
+    #[test]
+    fn overlaps_another_excludes_only_itself() {
+        let claims = sample();
+        let index = ClaimIndex::new(&claims);
+
+        assert!(index.overlaps_another(0));
+        assert!(index.overlaps_another(1));
+        assert!(!index.overlaps_another(2));
+
+        let lone = [claims[2]];
+        assert!(!ClaimIndex::new(&lone).overlaps_another(0));
+
+        // a copy of a claim still overlaps the original
+        let twins = [claims[2], claims[2]];
+        assert!(ClaimIndex::new(&twins).overlaps_another(0));
+    }
+
+    #[test]
+    fn far_and_negative_claims() {
+        let claims: Vec<FabricClaim> = [
+            "#1 @ -2000000000,-5: 10x10",
+            "#2 @ -1999999995,0: 10x10",
+            "#3 @ 2000000000,2000000000: 100000000x100000000",
+        ]
+        .iter()
+        .map(|line| line.parse().unwrap())
+        .collect();
+        let index = ClaimIndex::new(&claims);
+
+        assert_eq!(
+            vec![1, 2],
+            ids(index.claims_at(Point::new(-1_999_999_991, 1)))
+        );
+        assert_eq!(
+            vec![3],
+            ids(index.claims_in(Rect::new(2_099_999_999, 2_000_000_000, 5, 5)))
+        );
+        assert!(!index.overlaps_another(2));
+    }
+
+    #[test]
+    fn empty_index_finds_nothing() {
+        let index = ClaimIndex::new(&[]);
+
+        assert!(index.claims_in(Rect::new(0, 0, 100, 100)).is_empty());
+    }
+
+    #[test]
+    fn matches_checking_every_claim() {
+        let mut rng = XorShift::new(0x7a11_c0de);
+
+        let claims: Vec<FabricClaim> = (1..=500)
+            .map(|id| FabricClaim {
+                id,
+                rect: Rect::new(
+                    rng.between(-500, 499),
+                    rng.between(-500, 499),
+                    rng.between(0, 59),
+                    rng.between(0, 59),
+                ),
+            })
+            .collect();
+        let index = ClaimIndex::new(&claims);
+
+        for _ in 0..200 {
+            let point = Point::new(rng.between(-550, 549), rng.between(-550, 549));
+            let area = Rect::new(point.x, point.y, rng.between(0, 99), rng.between(0, 99));
+
+            let at: Vec<i32> = claims
+                .iter()
+                .filter(|c| c.rect.contains(point))
+                .map(|c| c.id)
+                .collect();
+            let within: Vec<i32> = claims
+                .iter()
+                .filter(|c| c.rect.intersects(&area))
+                .map(|c| c.id)
+                .collect();
+
+            assert_eq!(at, ids(index.claims_at(point)));
+            assert_eq!(within, ids(index.claims_in(area)));
+        }
+    }
+}
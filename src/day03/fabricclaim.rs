@@ -1,101 +1,159 @@
-use regex::Regex;
-use std::error;
-use std::fmt;
-use std::num::ParseIntError;
-use std::str::FromStr;
-
-#[derive(Debug)]
-/// Represents a claim about a cut of fabric
-pub struct FabricClaim {
-    /// The id of the claim
-    pub id: i32,
-
-    ///
-    /// The distance in inches from the left side of the fabric
-    pub left: i32,
-
-    /// THe distance in inches from the top of the fabric
-    pub top: i32,
-
-    /// The width in inches of the cut
-    pub width: i32,
-
-    /// The height in inches of the cut
-    pub height: i32,
-}
-
-impl FromStr for FabricClaim {
-    type Err = ParseFabricClaimError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new("^#(\\d+) @ (\\d+),(\\d+): (\\d+)x(\\d+)$").unwrap();
-        }
-
-        for capture in RE.captures_iter(s) {
-            let id: i32 = capture[1].parse()?;
-            let left: i32 = capture[2].parse()?;
-            let top: i32 = capture[3].parse()?;
-            let width: i32 = capture[4].parse()?;
-            let height: i32 = capture[5].parse()?;
-
-            return Ok(FabricClaim {
-                id,
-                left,
-                top,
-                width,
-                height,
-            });
-        }
-
-        Err(ParseFabricClaimError::InvalidFormat(String::from(s)))
-    }
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub enum ParseFabricClaimError {
-    InvalidFormat(String),
-    UnparsableToken,
-}
-
-impl From<ParseIntError> for ParseFabricClaimError {
-    fn from(_: ParseIntError) -> Self {
-        ParseFabricClaimError::UnparsableToken
-    }
-}
-
-impl fmt::Display for ParseFabricClaimError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            ParseFabricClaimError::InvalidFormat(s) => write!(f, "Invalid format: '{}'", s),
-            ParseFabricClaimError::UnparsableToken => {
-                f.write_str("Part of the string could not be converted")
-            }
-        }
-    }
-}
-
-impl error::Error for ParseFabricClaimError {}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn parse_success() {
-        let claim: FabricClaim = "#1 @ 100,366: 24x27".parse().unwrap();
-
-        assert_eq!(1, claim.id);
-        assert_eq!(100, claim.left);
-        assert_eq!(366, claim.top);
-        assert_eq!(24, claim.width);
-        assert_eq!(27, claim.height);
-    }
-
-    #[test]
-    fn parse_unparsable() {
-        let claim = "dsjghdklgjdfk".parse::<FabricClaim>();
-
-        assert!(claim.is_err());
-    }
-}
+use regex::Regex;
+use std::error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use geometry::Rect;
+
+/// Represents a claim about a cut of fabric
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FabricClaim {
+    /// The id of the claim
+    pub id: i32,
+
+    /// The cut, in inches from the top left corner of the fabric
+    pub rect: Rect,
+}
+
+impl FromStr for FabricClaim {
+    type Err = ParseFabricClaimError;
+
+    /// Reads a claim like `#123 @ 3,2: 5x4`. Spaces are allowed around every
+    /// part and the `#` is optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(
+                r"^\s*#?\s*(\S+?)\s*@\s*(\S+?)\s*,\s*(\S+?)\s*:\s*(\S+?)\s*x\s*(\S+?)\s*$"
+            )
+            .unwrap();
+        }
+
+        let capture = RE
+            .captures(s)
+            .ok_or_else(|| ParseFabricClaimError::InvalidFormat(String::from(s)))?;
+
+        let field = |index: usize, name: &'static str| -> Result<i32, ParseFabricClaimError> {
+            let token = &capture[index];
+            token
+                .parse()
+                .map_err(|err| ParseFabricClaimError::InvalidField {
+                    field: name,
+                    token: String::from(token),
+                    err,
+                })
+        };
+
+        Ok(FabricClaim {
+            id: field(1, "id")?,
+            rect: Rect::new(
+                field(2, "left")?,
+                field(3, "top")?,
+                field(4, "width")?,
+                field(5, "height")?,
+            ),
+        })
+    }
+}
+
+impl fmt::Display for FabricClaim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#{} @ {},{}: {}x{}",
+            self.id, self.rect.left, self.rect.top, self.rect.width, self.rect.height
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseFabricClaimError {
+    InvalidFormat(String),
+    InvalidField {
+        field: &'static str,
+        token: String,
+        err: ParseIntError,
+    },
+}
+
+impl fmt::Display for ParseFabricClaimError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseFabricClaimError::InvalidFormat(s) => write!(f, "Invalid format: '{}'", s),
+            ParseFabricClaimError::InvalidField { field, token, err } => {
+                write!(f, "Invalid {} '{}': {}", field, token, err)
+            }
+        }
+    }
+}
+
+impl error::Error for ParseFabricClaimError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_util::XorShift;
+
+    #[test]
+    fn parse_success() {
+        let claim: FabricClaim = "#1 @ 100,366: 24x27".parse().unwrap();
+
+        assert_eq!(1, claim.id);
+        assert_eq!(Rect::new(100, 366, 24, 27), claim.rect);
+    }
+
+    #[test]
+    fn parse_unparsable() {
+        let claim = "dsjghdklgjdfk".parse::<FabricClaim>();
+
+        assert!(claim.is_err());
+    }
+
+    #[test]
+    fn parse_tolerates_spaces_and_missing_hash() {
+        let expected = FabricClaim {
+            id: 7,
+            rect: Rect::new(1, 2, 3, 4),
+        };
+
+        assert_eq!(Ok(expected), "  # 7  @ 1 , 2 :3 x 4 ".parse());
+        assert_eq!(Ok(expected), "7 @ 1,2: 3x4".parse());
+    }
+
+    #[test]
+    fn parse_names_bad_field() {
+        let err = "#1 @ 1,3: 4xfour".parse::<FabricClaim>().unwrap_err();
+
+        assert_eq!(
+            "Invalid height 'four': invalid digit found in string",
+            err.to_string()
+        );
+
+        let err = "#1 @ 99999999999,3: 4x4"
+            .parse::<FabricClaim>()
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Invalid left '99999999999'"));
+    }
+
+    #[test]
+    fn display_writes_puzzle_format() {
+        let claim: FabricClaim = " #12 @3,2 :5x4".parse().unwrap();
+
+        assert_eq!("#12 @ 3,2: 5x4", claim.to_string());
+    }
+
+    #[test]
+    fn parse_reads_back_display() {
+        let mut rng = XorShift::new(0x5eed_1234);
+        let mut next = || rng.next_u32() as i32;
+
+        for _ in 0..1000 {
+            let claim = FabricClaim {
+                id: next(),
+                rect: Rect::new(next(), next(), next(), next()),
+            };
+
+            assert_eq!(Ok(claim), claim.to_string().parse());
+        }
+    }
+}
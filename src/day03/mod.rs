@@ -1,166 +1,251 @@
-mod fabricclaim;
-
-use self::fabricclaim::FabricClaim;
-use std::error;
-use std::fs::File;
-use std::io::BufRead;
-use std::io::BufReader;
-
-pub fn read_input(filename: &str) -> Result<Vec<FabricClaim>, Box<error::Error>> {
-    let f = File::open(filename)?;
-    let reader = BufReader::new(f);
-
-    let mut results = vec![];
-    for line in reader.lines() {
-        let line = line.unwrap();
-
-        if line.len() > 0 {
-            let claim: FabricClaim = line.parse().unwrap();
-            results.push(claim);
-        }
-    }
-
-    Ok(results)
-}
-
-fn find_needed_size(claims: &Vec<FabricClaim>) -> (i32, i32) {
-    let mut max_height = 0;
-    let mut max_width = 0;
-
-    for claim in claims.iter() {
-        if claim.left + claim.width > max_width {
-            max_width = claim.left + claim.width;
-        }
-
-        if claim.top + claim.height > max_height {
-            max_height = claim.top + claim.height
-        }
-    }
-
-    (max_width, max_height)
-}
-
-pub fn calculate_overlap_area(claims: &Vec<FabricClaim>) -> usize {
-    let (width, height) = find_needed_size(&claims);
-
-    let mut fabric = vec![vec![0i32; width as usize]; height as usize];
-
-    for claim in claims.iter() {
-        apply(&mut fabric, &claim);
-    }
-
-    let count = fabric
-        .iter()
-        .flat_map(|row| row.iter())
-        .filter(|cell| *cell > &1)
-        .count();
-
-    count
-}
-
-fn apply(fabric: &mut Vec<Vec<i32>>, claim: &FabricClaim) {
-    for x in 0..claim.width {
-        for y in 0..claim.height {
-            let i = (claim.top + y) as usize;
-            let j = (claim.left + x) as usize;
-            fabric[i][j] += 1
-        }
-    }
-}
-
-pub fn find_non_overlapping_claim(claims: &Vec<FabricClaim>) -> Option<&FabricClaim> {
-    let (width, height) = find_needed_size(&claims);
-
-    let mut fabric = vec![vec![0i32; width as usize]; height as usize];
-
-    for claim in claims.iter() {
-        apply(&mut fabric, &claim);
-    }
-
-    for claim in claims.iter() {
-        if is_non_overlapping(claim, &fabric) {
-            return Some(claim);
-        }
-    }
-
-    None
-}
-
-fn is_non_overlapping(claim: &FabricClaim, fabric: &Vec<Vec<i32>>) -> bool {
-    for x in 0..claim.width {
-        for y in 0..claim.height {
-            let i = (claim.top + y) as usize;
-            let j = (claim.left + x) as usize;
-            if fabric[i][j] > 1 {
-                return false;
-            }
-        }
-    }
-
-    true
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn read_input_gets_line_count() {
-        let claims = read_input("inputs\\day03.txt").unwrap();
-
-        assert_eq!(1335, claims.len());
-    }
-
-    #[test]
-    fn find_needed_size_gets_it_right() {
-        let claims = read_input("inputs\\day03.txt").unwrap();
-
-        let (width, height) = find_needed_size(&claims);
-
-        assert_eq!(999, width);
-        assert_eq!(998, height);
-    }
-
-    #[test]
-    fn calculate_overlap_area_sample() {
-        let claims = vec![
-            "#1 @ 1,3: 4x4".parse().unwrap(),
-            "#2 @ 3,1: 4x4".parse().unwrap(),
-            "#3 @ 5,5: 2x2".parse().unwrap(),
-        ];
-
-        let area = calculate_overlap_area(&claims);
-
-        assert_eq!(4, area);
-    }
-
-    #[test]
-    fn calculate_overlap_area_is_correct() {
-        let claims = read_input("inputs\\day03.txt").unwrap();
-        let area = calculate_overlap_area(&claims);
-
-        assert_eq!(110383, area);
-    }
-
-    #[test]
-    fn find_non_overlapping_claim_sample() {
-        let claims = vec![
-            "#1 @ 1,3: 4x4".parse().unwrap(),
-            "#2 @ 3,1: 4x4".parse().unwrap(),
-            "#3 @ 5,5: 2x2".parse().unwrap(),
-        ];
-
-        let non_overlapper = find_non_overlapping_claim(&claims).unwrap();
-
-        assert_eq!(3, non_overlapper.id);
-    }
-
-    #[test]
-    fn find_non_overlapping_claim_real() {
-        let claims = read_input("inputs\\day03.txt").unwrap();
-
-        let non_overlapper = find_non_overlapping_claim(&claims).unwrap();
-
-        assert_eq!(129, non_overlapper.id);
-    }
-}
+mod analysis;
+mod claim_index;
+mod fabricclaim;
+mod render;
+mod sweep;
+mod validate;
+
+pub use self::analysis::FabricAnalysis;
+pub use self::claim_index::ClaimIndex;
+pub use self::fabricclaim::{FabricClaim, ParseFabricClaimError};
+pub use self::render::{ascii, write_pgm, write_ppm};
+pub use self::sweep::{
+    coverage, non_overlapping_claims, overlap_area, overlapping_pairs, regions_covered_by,
+};
+pub use self::validate::{validate, ClaimProblem, ClaimWarning, Validated, Validation};
+
+use std::convert::TryFrom;
+use std::io::BufRead;
+use std::path::Path;
+
+use error::{Error, Result};
+use geometry::Point;
+use grid::Grid;
+use input;
+use solver::Solver;
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    type Input = Vec<FabricClaim>;
+    type Part1 = u64;
+    type Part2 = i32;
+
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "No Matter How You Slice It";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(validate(parse_input(input)?, Validation::Strict)?.claims)
+    }
+
+    fn part1(&self, claims: &Self::Input) -> Result<Self::Part1> {
+        Ok(calculate_overlap_area(claims))
+    }
+
+    fn part2(&self, claims: &Self::Input) -> Result<Self::Part2> {
+        let claim = find_non_overlapping_claim(claims)
+            .ok_or_else(|| Error::domain("every claim overlaps another"))?;
+        Ok(claim.id)
+    }
+}
+
+pub fn read_input<P: AsRef<Path>>(filename: P) -> Result<Vec<FabricClaim>> {
+    input::parse_file(filename, read_from)
+}
+
+/// Reads claims from anything buffered, like an open file or stdin
+pub fn read_from<R: BufRead>(reader: R) -> Result<Vec<FabricClaim>> {
+    parse_input(&input::read_from(reader)?)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<FabricClaim>> {
+    input::parse_lines(input)
+}
+
+fn find_needed_size(claims: &[FabricClaim]) -> (i32, i32) {
+    let max_width = claims.iter().map(|c| c.rect.right()).max().unwrap_or(0);
+    let max_height = claims.iter().map(|c| c.rect.bottom()).max().unwrap_or(0);
+
+    (max_width, max_height)
+}
+
+/// The claim with an id, if there is one
+pub fn find_claim(claims: &[FabricClaim], id: i32) -> Option<&FabricClaim> {
+    claims.iter().find(|claim| claim.id == id)
+}
+
+/// The area covered by two or more claims
+pub fn calculate_overlap_area(claims: &[FabricClaim]) -> u64 {
+    overlap_area(claims)
+}
+
+/// The first claim that doesn't share any square inch with another
+pub fn find_non_overlapping_claim(claims: &[FabricClaim]) -> Option<&FabricClaim> {
+    let index = ClaimIndex::new(claims);
+
+    (0..claims.len())
+        .find(|&i| !index.overlaps_another(i))
+        .map(|i| &claims[i])
+}
+
+/// The area covered by two or more claims, counted square inch by square
+/// inch on a grid covering the whole fabric. Kept as a reference for the
+/// sweep, it needs every claim to be inside the fabric's positive quarter
+/// and memory for every square inch.
+pub fn dense_overlap_area(claims: &[FabricClaim]) -> u64 {
+    let fabric = cover(claims);
+
+    fabric.cells().iter().filter(|cell| **cell > 1).count() as u64
+}
+
+/// Every claim that doesn't share any square inch with another, found on a
+/// grid covering the whole fabric like `dense_overlap_area`
+pub fn dense_non_overlapping_claims(claims: &[FabricClaim]) -> Vec<&FabricClaim> {
+    let fabric = cover(claims);
+
+    claims
+        .iter()
+        .filter(|claim| is_non_overlapping(claim, &fabric))
+        .collect()
+}
+
+/// Counts how many claims cover each square inch of the fabric, on a grid
+/// reaching from the top left corner to the furthest claim. Anything at
+/// negative coordinates is left off the grid.
+pub fn cover(claims: &[FabricClaim]) -> Grid<i32> {
+    let (width, height) = find_needed_size(claims);
+    let mut fabric = Grid::new(width.max(0) as usize, height.max(0) as usize, 0i32);
+
+    for claim in claims.iter() {
+        for p in claim.rect.points() {
+            if let Some(count) = cell(p).and_then(|(x, y)| fabric.get_mut(x, y)) {
+                *count += 1;
+            }
+        }
+    }
+
+    fabric
+}
+
+fn is_non_overlapping(claim: &FabricClaim, fabric: &Grid<i32>) -> bool {
+    claim
+        .rect
+        .points()
+        .all(|p| match cell(p).and_then(|(x, y)| fabric.get(x, y)) {
+            Some(count) => *count <= 1,
+            None => true,
+        })
+}
+
+/// Where a point is on the fabric grid, if it isn't at negative coordinates
+fn cell(p: Point) -> Option<(usize, usize)> {
+    Some((usize::try_from(p.x).ok()?, usize::try_from(p.y).ok()?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use input;
+
+    const SAMPLE: &str = "#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2
+";
+
+    #[test]
+    fn read_input_gets_line_count() {
+        let claims = read_input(input::path(3)).unwrap();
+
+        assert_eq!(1335, claims.len());
+    }
+
+    #[test]
+    fn find_needed_size_gets_it_right() {
+        let claims = read_input(input::path(3)).unwrap();
+
+        let (width, height) = find_needed_size(&claims);
+
+        assert_eq!(999, width);
+        assert_eq!(998, height);
+    }
+
+    #[test]
+    fn calculate_overlap_area_sample() {
+        let claims = read_from(SAMPLE.as_bytes()).unwrap();
+
+        let area = calculate_overlap_area(&claims);
+
+        assert_eq!(4, area);
+    }
+
+    #[test]
+    fn calculate_overlap_area_is_correct() {
+        let claims = read_input(input::path(3)).unwrap();
+        let area = calculate_overlap_area(&claims);
+
+        assert_eq!(110383, area);
+    }
+
+    #[test]
+    fn dense_overlap_area_is_correct() {
+        let claims = read_input(input::path(3)).unwrap();
+
+        assert_eq!(110383, dense_overlap_area(&claims));
+        assert_eq!(129, dense_non_overlapping_claims(&claims)[0].id);
+    }
+
+    #[test]
+    fn find_non_overlapping_claim_far_and_negative() {
+        let claims = parse_input(
+            "#1 @ -2000000000,-5: 10x10\n\
+             #2 @ -1999999995,0: 10x10\n\
+             #3 @ 2000000000,2000000000: 100000000x100000000\n",
+        )
+        .unwrap();
+
+        assert_eq!(3, find_non_overlapping_claim(&claims).unwrap().id);
+    }
+
+    #[test]
+    fn dense_fabric_ignores_negative_coordinates() {
+        let claims = parse_input("#1 @ -2,-2: 4x4\n#2 @ 1,1: 2x2\n").unwrap();
+
+        assert_eq!(1, dense_overlap_area(&claims));
+        assert_eq!(1, overlap_area(&claims));
+    }
+
+    #[test]
+    fn solver_rejects_invalid_claims() {
+        let err = Day03.parse("#1 @ 1,3: 4x4\n#1 @ 3,1: 4x4\n").unwrap_err();
+
+        assert_eq!("Claim 2 (#1) has the same id as claim 1", err.to_string());
+    }
+
+    #[test]
+    fn find_non_overlapping_claim_sample() {
+        let claims = read_from(SAMPLE.as_bytes()).unwrap();
+
+        let non_overlapper = find_non_overlapping_claim(&claims).unwrap();
+
+        assert_eq!(3, non_overlapper.id);
+    }
+
+    #[test]
+    fn find_non_overlapping_claim_real() {
+        let claims = read_input(input::path(3)).unwrap();
+
+        let non_overlapper = find_non_overlapping_claim(&claims).unwrap();
+
+        assert_eq!(129, non_overlapper.id);
+    }
+
+    #[test]
+    fn parse_input_reports_bad_line() {
+        let err = parse_input("#1 @ 1,3: 4x4\n#2 @ 3,1 4x4\n").unwrap_err();
+
+        assert_eq!(
+            "line 2, column 1: Invalid format: '#2 @ 3,1 4x4'",
+            err.to_string()
+        );
+    }
+}
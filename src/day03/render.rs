@@ -0,0 +1,186 @@
+use std::cmp;
+use std::io::{self, Write};
+
+use geometry::{Point, Rect};
+use grid::Grid;
+
+use super::FabricClaim;
+
+/// Colours for square inches claimed 0, 1, 2 and 3 or more times
+const PALETTE: [[u8; 3]; 4] = [[0, 0, 0], [70, 110, 180], [240, 160, 40], [220, 30, 30]];
+
+/// Colours for the highlighted claim where nothing else claims it, and where
+/// something else does
+const HIGHLIGHT: [[u8; 3]; 2] = [[60, 200, 60], [250, 240, 60]];
+
+/// Writes the fabric as a greyscale binary PGM image, from black where
+/// nothing is claimed to white where the most claims overlap
+pub fn write_pgm<W: Write>(mut out: W, fabric: &Grid<i32>) -> io::Result<()> {
+    let most = cmp::max(1, fabric.cells().iter().cloned().max().unwrap_or(0));
+
+    write!(out, "P5\n{} {}\n255\n", fabric.width(), fabric.height())?;
+
+    let pixels: Vec<u8> = fabric
+        .cells()
+        .iter()
+        .map(|count| (i64::from(cmp::max(*count, 0)) * 255 / i64::from(most)) as u8)
+        .collect();
+
+    out.write_all(&pixels)?;
+    out.flush()
+}
+
+/// Writes the fabric as a colour binary PPM image, coloured by how many
+/// claims cover each square inch. A highlighted claim is drawn in green,
+/// turning yellow where it overlaps something else.
+pub fn write_ppm<W: Write>(
+    mut out: W,
+    fabric: &Grid<i32>,
+    highlight: Option<&FabricClaim>,
+) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", fabric.width(), fabric.height())?;
+
+    let mut pixels = Vec::with_capacity(fabric.cells().len() * 3);
+    for ((x, y), count) in fabric.iter() {
+        let colour = if is_highlighted(highlight, x, y) {
+            HIGHLIGHT[(*count - 1).clamp(0, 1) as usize]
+        } else {
+            PALETTE[(*count).clamp(0, 3) as usize]
+        };
+
+        pixels.extend_from_slice(&colour);
+    }
+
+    out.write_all(&pixels)?;
+    out.flush()
+}
+
+/// Draws the part of the fabric inside `crop` as text, one line per row.
+/// Square inches show `.` when unclaimed, the number of claims up to 9, and
+/// `*` for more. A highlighted claim shows `#` where nothing else claims it
+/// and `X` where something does. Anything of `crop` outside the fabric is
+/// left out.
+pub fn ascii(fabric: &Grid<i32>, crop: Rect, highlight: Option<&FabricClaim>) -> String {
+    let bounds = Rect::new(0, 0, fabric.width() as i32, fabric.height() as i32);
+    let visible = match bounds.intersection(&crop) {
+        Some(visible) => visible,
+        None => return String::new(),
+    };
+
+    let mut result = String::new();
+    for y in visible.top..visible.bottom() {
+        for x in visible.left..visible.right() {
+            let (x, y) = (x as usize, y as usize);
+            let count = fabric[(x, y)];
+
+            let symbol = if is_highlighted(highlight, x, y) {
+                if count > 1 {
+                    'X'
+                } else {
+                    '#'
+                }
+            } else {
+                match count {
+                    c if c <= 0 => '.',
+                    c @ 1..=9 => (b'0' + c as u8) as char,
+                    _ => '*',
+                }
+            };
+
+            result.push(symbol);
+        }
+        result.push('\n');
+    }
+
+    result
+}
+
+fn is_highlighted(highlight: Option<&FabricClaim>, x: usize, y: usize) -> bool {
+    match highlight {
+        Some(claim) => claim.rect.contains(Point::new(x as i32, y as i32)),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::cover;
+    use super::*;
+
+    fn sample() -> Vec<FabricClaim> {
+        ["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2"]
+            .iter()
+            .map(|line| line.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn ascii_draws_counts() {
+        let fabric = cover(&sample());
+
+        assert_eq!(
+            "\
+.......
+...1111
+...1111
+.112211
+.112211
+.111111
+.111111
+",
+            ascii(&fabric, Rect::new(0, 0, 7, 7), None)
+        );
+    }
+
+    #[test]
+    fn ascii_crops_and_highlights() {
+        let claims = sample();
+        let fabric = cover(&claims);
+
+        assert_eq!(
+            "XX##\nXX##\n",
+            ascii(&fabric, Rect::new(3, 3, 10, 2), Some(&claims[1]))
+        );
+        assert_eq!(
+            "1XX#\n",
+            ascii(&fabric, Rect::new(2, 3, 4, 1), Some(&claims[1]))
+        );
+        assert_eq!("", ascii(&fabric, Rect::new(-5, -5, 2, 2), None));
+    }
+
+    #[test]
+    fn pgm_has_header_and_scaled_pixels() {
+        let fabric = cover(&sample());
+        let mut image = vec![];
+        write_pgm(&mut image, &fabric).unwrap();
+
+        let header = b"P5\n7 7\n255\n";
+        assert_eq!(&header[..], &image[..header.len()]);
+        assert_eq!(header.len() + 49, image.len());
+
+        // the overlap at 3,3 is the most claimed
+        assert_eq!(255, image[header.len() + 3 * 7 + 3]);
+        assert_eq!(127, image[header.len() + 3 * 7 + 1]);
+        assert_eq!(0, image[header.len()]);
+    }
+
+    #[test]
+    fn ppm_colours_by_count_and_highlight() {
+        let claims = sample();
+        let fabric = cover(&claims);
+        let mut image = vec![];
+        write_ppm(&mut image, &fabric, Some(&claims[2])).unwrap();
+
+        let header = b"P6\n7 7\n255\n";
+        let pixel = |x: usize, y: usize| {
+            let at = header.len() + (y * 7 + x) * 3;
+            [image[at], image[at + 1], image[at + 2]]
+        };
+
+        assert_eq!(&header[..], &image[..header.len()]);
+        assert_eq!(PALETTE[0], pixel(0, 0));
+        assert_eq!(PALETTE[1], pixel(1, 3));
+        assert_eq!(PALETTE[2], pixel(3, 3));
+        assert_eq!(HIGHLIGHT[0], pixel(5, 5));
+    }
+}
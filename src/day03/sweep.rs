@@ -0,0 +1,308 @@
+use geometry::{Point, Rect};
+
+use super::FabricClaim;
+
+/// The area covered by two or more claims, found without laying out the
+/// fabric, so claims can sit anywhere in the `i32` plane
+pub fn overlap_area(claims: &[FabricClaim]) -> u64 {
+    regions_covered_by(claims, 2).iter().map(Rect::area).sum()
+}
+
+/// Rectangles covering every square inch shared by at least `times` claims,
+/// without overlapping each other. Claims can sit anywhere in the `i32`
+/// plane. A `times` of 0 is treated as 1, since the unclaimed fabric goes on
+/// forever.
+///
+/// Only the columns where a claim starts or ends matter, so the fabric is cut
+/// into strips between them. A line then sweeps down the fabric, stopping
+/// where claims start or end, and keeps track of how many claims cover each
+/// strip. Memory grows with the number of claims rather than the size of the
+/// fabric.
+pub fn regions_covered_by(claims: &[FabricClaim], times: u32) -> Vec<Rect> {
+    let times = times.max(1);
+    let mut regions = vec![];
+
+    sweep(claims, |columns, cover, top, bottom| {
+        let mut start = None;
+
+        // one past the last strip to close a run reaching the right edge
+        for i in 0..=cover.len() {
+            let covered = i < cover.len() && cover[i] >= times;
+
+            match start {
+                None if covered => start = Some(i),
+                Some(first) if !covered => {
+                    add_run(&mut regions, &columns[first..=i], top, bottom);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+    });
+
+    regions
+}
+
+/// Every part of the fabric covered by at least one claim, as rectangles
+/// that don't overlap each other, each with the number of claims covering
+/// it. Found with the same sweep as `regions_covered_by`.
+pub fn coverage(claims: &[FabricClaim]) -> Vec<(Rect, u32)> {
+    let mut regions = vec![];
+
+    sweep(claims, |columns, cover, top, bottom| {
+        let mut start = 0;
+
+        // one past the last strip to close a run reaching the right edge
+        for i in 1..=cover.len() {
+            if i < cover.len() && cover[i] == cover[start] {
+                continue;
+            }
+
+            if cover[start] > 0 {
+                let mut rects = vec![];
+                add_run(&mut rects, &columns[start..=i], top, bottom);
+                regions.extend(rects.into_iter().map(|rect| (rect, cover[start])));
+            }
+            start = i;
+        }
+    });
+
+    regions
+}
+
+/// Sweeps down the fabric, calling `band` with the strip edges, how many
+/// claims cover each strip, and the rows between which that holds
+fn sweep<F: FnMut(&[i32], &[u32], i32, i32)>(claims: &[FabricClaim], mut band: F) {
+    let rects: Vec<Rect> = claims
+        .iter()
+        .map(|claim| claim.rect)
+        .filter(|rect| !rect.is_empty())
+        .collect();
+
+    let mut columns: Vec<i32> = rects.iter().flat_map(|r| vec![r.left, r.right()]).collect();
+    columns.sort_unstable();
+    columns.dedup();
+
+    let strip = |x: i32| columns.binary_search(&x).unwrap_or_else(|i| i);
+
+    // (row, change in cover, first strip, strip past the last)
+    let mut events: Vec<(i32, i32, usize, usize)> = rects
+        .iter()
+        .flat_map(|r| {
+            let (from, to) = (strip(r.left), strip(r.right()));
+            vec![(r.top, 1, from, to), (r.bottom(), -1, from, to)]
+        })
+        .collect();
+    events.sort_unstable();
+
+    let mut cover = vec![0u32; columns.len().saturating_sub(1)];
+    let mut row = events.first().map_or(0, |e| e.0);
+
+    for (y, change, from, to) in events {
+        if y > row {
+            band(&columns, &cover, row, y);
+            row = y;
+        }
+
+        for count in cover[from..to].iter_mut() {
+            if change > 0 {
+                *count += 1;
+            } else {
+                *count -= 1;
+            }
+        }
+    }
+}
+
+/// Adds rectangles covering the strips between the first and last of
+/// `edges`, split wherever one rectangle would be wider than an `i32` can
+/// hold. A single strip always fits, since it lies within one claim.
+fn add_run(regions: &mut Vec<Rect>, edges: &[i32], top: i32, bottom: i32) {
+    let mut left = 0;
+
+    for right in 1..edges.len() {
+        let last = right + 1 == edges.len();
+        let too_wide = !last && edges[right + 1].checked_sub(edges[left]).is_none();
+
+        if last || too_wide {
+            regions.push(Rect::from_corners(
+                Point::new(edges[left], top),
+                Point::new(edges[right], bottom),
+            ));
+            left = right;
+        }
+    }
+}
+
+/// Every claim that doesn't share any square inch with another, in the order
+/// given
+pub fn non_overlapping_claims(claims: &[FabricClaim]) -> Vec<&FabricClaim> {
+    let mut overlapping = vec![false; claims.len()];
+
+    for (i, j) in overlapping_pairs(claims) {
+        overlapping[i] = true;
+        overlapping[j] = true;
+    }
+
+    claims
+        .iter()
+        .zip(overlapping)
+        .filter(|(_, overlapping)| !overlapping)
+        .map(|(claim, _)| claim)
+        .collect()
+}
+
+/// Every pair of claims sharing at least one square inch, as indexes into
+/// `claims` with the earlier one first, in order.
+///
+/// Claims are sorted by their left edge, so each only needs checking against
+/// the ones starting before its right edge.
+pub fn overlapping_pairs(claims: &[FabricClaim]) -> Vec<(usize, usize)> {
+    let mut by_left: Vec<usize> = (0..claims.len()).collect();
+    by_left.sort_by_key(|&i| claims[i].rect.left);
+
+    let mut pairs = vec![];
+
+    for (n, &i) in by_left.iter().enumerate() {
+        let rect = &claims[i].rect;
+
+        for &j in by_left[n + 1..].iter() {
+            let other = &claims[j].rect;
+            if other.left >= rect.right() {
+                break;
+            }
+
+            if rect.intersects(other) {
+                pairs.push((i.min(j), i.max(j)));
+            }
+        }
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{dense_non_overlapping_claims, dense_overlap_area};
+    use super::*;
+    use test_util::XorShift;
+
+    fn claim(id: i32, left: i32, top: i32, width: i32, height: i32) -> FabricClaim {
+        FabricClaim {
+            id,
+            rect: Rect::new(left, top, width, height),
+        }
+    }
+
+    fn sample() -> Vec<FabricClaim> {
+        vec![
+            claim(1, 1, 3, 4, 4),
+            claim(2, 3, 1, 4, 4),
+            claim(3, 5, 5, 2, 2),
+        ]
+    }
+
+    #[test]
+    fn overlap_area_sample() {
+        assert_eq!(4, overlap_area(&sample()));
+        assert_eq!(0, overlap_area(&[]));
+    }
+
+    #[test]
+    fn regions_covered_by_sample() {
+        let claims = sample();
+
+        assert_eq!(vec![Rect::new(3, 3, 2, 2)], regions_covered_by(&claims, 2));
+        assert!(regions_covered_by(&claims, 3).is_empty());
+        assert_eq!(
+            32,
+            regions_covered_by(&claims, 1)
+                .iter()
+                .map(Rect::area)
+                .sum::<u64>()
+        );
+    }
+
+    #[test]
+    fn coverage_counts_each_region() {
+        let mut regions = coverage(&sample());
+        regions.sort_by_key(|(rect, _)| (rect.top, rect.left));
+
+        let doubled: Vec<Rect> = regions
+            .iter()
+            .filter(|(_, count)| *count == 2)
+            .map(|(rect, _)| *rect)
+            .collect();
+
+        assert_eq!(vec![Rect::new(3, 3, 2, 2)], doubled);
+        assert_eq!(32, regions.iter().map(|(rect, _)| rect.area()).sum::<u64>());
+    }
+
+    #[test]
+    fn regions_wider_than_i32_are_split() {
+        let claims = vec![
+            claim(1, -2_000_000_000, 0, 2_000_000_000, 1),
+            claim(2, 0, 0, 2_000_000_000, 1),
+        ];
+        let regions = regions_covered_by(&claims, 1);
+
+        assert_eq!(
+            vec![
+                Rect::new(-2_000_000_000, 0, 2_000_000_000, 1),
+                Rect::new(0, 0, 2_000_000_000, 1)
+            ],
+            regions
+        );
+        assert_eq!(regions, regions_covered_by(&claims, 0));
+    }
+
+    #[test]
+    fn non_overlapping_claims_sample() {
+        let claims = sample();
+        let ids: Vec<i32> = non_overlapping_claims(&claims)
+            .iter()
+            .map(|c| c.id)
+            .collect();
+
+        assert_eq!(vec![3], ids);
+    }
+
+    #[test]
+    fn handles_far_and_negative_claims() {
+        let claims = vec![
+            claim(1, -2_000_000_000, -5, 10, 10),
+            claim(2, -1_999_999_995, 0, 10, 10),
+            claim(3, 2_000_000_000, 2_000_000_000, 100_000_000, 100_000_000),
+        ];
+
+        assert_eq!(25, overlap_area(&claims));
+        assert_eq!(
+            vec![3],
+            non_overlapping_claims(&claims)
+                .iter()
+                .map(|c| c.id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn matches_dense_fabric() {
+        let mut rng = XorShift::new(0x0bad_5eed);
+        let mut next = |n: u32| rng.below(n) as i32;
+
+        for _ in 0..100 {
+            let claims: Vec<FabricClaim> = (1..=12)
+                .map(|id| claim(id, next(30), next(30), next(10), next(10)))
+                .collect();
+
+            let ids = |found: Vec<&FabricClaim>| found.iter().map(|c| c.id).collect::<Vec<_>>();
+
+            assert_eq!(dense_overlap_area(&claims), overlap_area(&claims));
+            assert_eq!(
+                ids(dense_non_overlapping_claims(&claims)),
+                ids(non_overlapping_claims(&claims))
+            );
+        }
+    }
+}
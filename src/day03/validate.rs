@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use error::{Error, Result};
+
+use super::FabricClaim;
+
+/// What to do with claims that can't be right
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Validation {
+    /// Refuse the whole list if any claim has a problem
+    Strict,
+
+    /// Leave out claims with problems, with a warning for each
+    Lenient,
+}
+
+/// Something wrong with a claim
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClaimProblem {
+    /// An earlier claim, at this position in the list, has the same id
+    DuplicateId { first: usize },
+
+    /// The claim covers no fabric at all
+    ZeroSize,
+
+    /// A position or size is below 0
+    Negative(&'static str),
+
+    /// The right or bottom edge is past the largest `i32`
+    Overflow,
+}
+
+/// A problem with the claim at a position in the list, counting from 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimWarning {
+    pub index: usize,
+    pub id: i32,
+    pub problem: ClaimProblem,
+}
+
+impl fmt::Display for ClaimWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Claim {} (#{}) ", self.index + 1, self.id)?;
+
+        match self.problem {
+            ClaimProblem::DuplicateId { first } => {
+                write!(f, "has the same id as claim {}", first + 1)
+            }
+            ClaimProblem::ZeroSize => f.write_str("covers no fabric"),
+            ClaimProblem::Negative(field) => write!(f, "has a negative {}", field),
+            ClaimProblem::Overflow => f.write_str("reaches past the largest coordinate"),
+        }
+    }
+}
+
+/// The claims that passed validation, and warnings about any left out
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validated {
+    pub claims: Vec<FabricClaim>,
+    pub warnings: Vec<ClaimWarning>,
+}
+
+/// Checks every claim has a unique id and covers some fabric at positive
+/// coordinates without overflowing. A later claim reusing an id is the one
+/// with the problem.
+///
+/// In strict mode any problem is an error listing all of them. In lenient
+/// mode the claims with problems are left out and reported as warnings.
+pub fn validate(claims: Vec<FabricClaim>, mode: Validation) -> Result<Validated> {
+    let mut first_with_id = HashMap::new();
+    let mut valid = Vec::with_capacity(claims.len());
+    let mut warnings = vec![];
+
+    for (index, claim) in claims.into_iter().enumerate() {
+        let problem = match first_with_id.get(&claim.id) {
+            Some(&first) => Some(ClaimProblem::DuplicateId { first }),
+            None => geometry_problem(&claim),
+        };
+
+        first_with_id.entry(claim.id).or_insert(index);
+
+        match problem {
+            Some(problem) => warnings.push(ClaimWarning {
+                index,
+                id: claim.id,
+                problem,
+            }),
+            None => valid.push(claim),
+        }
+    }
+
+    if mode == Validation::Strict && !warnings.is_empty() {
+        let problems: Vec<String> = warnings.iter().map(ClaimWarning::to_string).collect();
+        return Err(Error::domain(problems.join("; ")));
+    }
+
+    Ok(Validated {
+        claims: valid,
+        warnings,
+    })
+}
+
+fn geometry_problem(claim: &FabricClaim) -> Option<ClaimProblem> {
+    let rect = &claim.rect;
+    let fields = [
+        ("left", rect.left),
+        ("top", rect.top),
+        ("width", rect.width),
+        ("height", rect.height),
+    ];
+
+    if let Some((field, _)) = fields.iter().find(|(_, value)| *value < 0) {
+        return Some(ClaimProblem::Negative(field));
+    }
+
+    if rect.left.checked_add(rect.width).is_none() || rect.top.checked_add(rect.height).is_none() {
+        return Some(ClaimProblem::Overflow);
+    }
+
+    if rect.is_empty() {
+        return Some(ClaimProblem::ZeroSize);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn claims(lines: &[&str]) -> Vec<FabricClaim> {
+        lines.iter().map(|line| line.parse().unwrap()).collect()
+    }
+
+    fn problems(validated: &Validated) -> Vec<(usize, ClaimProblem)> {
+        validated
+            .warnings
+            .iter()
+            .map(|w| (w.index, w.problem.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn valid_claims_pass_both_modes() {
+        let input = claims(&["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4"]);
+
+        for &mode in [Validation::Strict, Validation::Lenient].iter() {
+            let validated = validate(input.clone(), mode).unwrap();
+
+            assert_eq!(input, validated.claims);
+            assert!(validated.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn lenient_skips_problems_with_warnings() {
+        let input = claims(&[
+            "#1 @ 1,3: 4x4",
+            "#1 @ 3,1: 4x4",
+            "#2 @ 3,1: 0x4",
+            "#3 @ -1,1: 4x4",
+            "#4 @ 2147483647,1: 4x4",
+            "#5 @ 5,5: 2x2",
+        ]);
+
+        let validated = validate(input, Validation::Lenient).unwrap();
+        let ids: Vec<i32> = validated.claims.iter().map(|c| c.id).collect();
+
+        assert_eq!(vec![1, 5], ids);
+        assert_eq!(
+            vec![
+                (1, ClaimProblem::DuplicateId { first: 0 }),
+                (2, ClaimProblem::ZeroSize),
+                (3, ClaimProblem::Negative("left")),
+                (4, ClaimProblem::Overflow),
+            ],
+            problems(&validated)
+        );
+    }
+
+    #[test]
+    fn strict_rejects_with_every_problem() {
+        let input = claims(&["#1 @ 1,3: 4x4", "#1 @ 3,1: 4x4", "#2 @ 3,1: 4x-4"]);
+        let err = validate(input, Validation::Strict).unwrap_err();
+
+        assert_eq!(
+            "Claim 2 (#1) has the same id as claim 1; Claim 3 (#2) has a negative height",
+            err.to_string()
+        );
+    }
+}
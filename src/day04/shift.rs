@@ -0,0 +1,284 @@
+use chrono::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{check_sequence, GuardLog, GuardLogType};
+use error::{Error, Result};
+
+/// One guard's night on duty. Guards only ever sleep during the midnight
+/// hour, so that hour is all a shift records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shift {
+    pub guard_id: u32,
+
+    /// The day whose midnight hour the shift covers. A shift starting before
+    /// midnight belongs to the next day.
+    pub date: NaiveDate,
+
+    /// Bit `m` is set if the guard was asleep during minute `m` past
+    /// midnight
+    pub asleep: u64,
+}
+
+impl Shift {
+    /// Splits sorted log entries into shifts, one for each guard beginning
+    /// a shift. A guard still asleep when the shift ends sleeps until 01:00.
+    pub fn from_logs(logs: &[GuardLog]) -> Result<Vec<Shift>> {
+        check_sequence(logs)?;
+
+        let mut shifts: Vec<Shift> = vec![];
+        let mut fell_asleep: Option<u32> = None;
+
+        for log in logs.iter() {
+            if let GuardLogType::BeginShift(guard_id) = log.log_type {
+                if let (Some(current), Some(minute)) = (shifts.last_mut(), fell_asleep.take()) {
+                    current.sleep(minute, 60);
+                }
+
+                shifts.push(Shift::starting(guard_id, log.utc)?);
+                continue;
+            }
+
+            // check_sequence makes sure a shift has started
+            let shift = match shifts.last_mut() {
+                Some(shift) => shift,
+                None => continue,
+            };
+            let minute = shift.minute_of(log.utc)?;
+
+            match log.log_type {
+                GuardLogType::Sleep => {
+                    fell_asleep = fell_asleep.or(Some(minute));
+                }
+                GuardLogType::Wake => {
+                    if let Some(from) = fell_asleep.take() {
+                        shift.sleep(from, minute);
+                    }
+                }
+                GuardLogType::BeginShift(_) => {}
+            }
+        }
+
+        if let (Some(current), Some(minute)) = (shifts.last_mut(), fell_asleep) {
+            current.sleep(minute, 60);
+        }
+
+        Ok(shifts)
+    }
+
+    /// Groups shifts by the guard on duty, keeping each guard's shifts in
+    /// the order given
+    pub fn by_guard(shifts: &[Shift]) -> HashMap<u32, Vec<&Shift>> {
+        let mut by_guard: HashMap<u32, Vec<&Shift>> = HashMap::new();
+
+        for shift in shifts.iter() {
+            by_guard.entry(shift.guard_id).or_default().push(shift);
+        }
+
+        by_guard
+    }
+
+    /// A shift with nobody asleep yet, for a guard starting at `begins`
+    fn starting(guard_id: u32, begins: DateTime<Utc>) -> Result<Shift> {
+        let day = begins.naive_utc().date();
+        let date = if begins.hour() >= 12 {
+            day.succ_opt().ok_or_else(|| {
+                Error::domain(format!("A shift starting on {} has no next day", day))
+            })?
+        } else {
+            day
+        };
+
+        Ok(Shift {
+            guard_id,
+            date,
+            asleep: 0,
+        })
+    }
+
+    /// The minute past midnight of a time, or 0 if it's before midnight and
+    /// 60 if it's after the midnight hour
+    fn minute_of(&self, time: DateTime<Utc>) -> Result<u32> {
+        let midnight = self
+            .date
+            .and_hms_opt(0, 0, 0)
+            .ok_or_else(|| Error::domain(format!("The shift on {} has no midnight", self.date)))?;
+        let minutes = time
+            .naive_utc()
+            .signed_duration_since(midnight)
+            .num_minutes();
+
+        Ok(minutes.clamp(0, 60) as u32)
+    }
+
+    /// Marks the guard asleep from minute `from` up to, but not including,
+    /// minute `to`
+    fn sleep(&mut self, from: u32, to: u32) {
+        for minute in from..to.min(60) {
+            self.asleep |= 1 << minute;
+        }
+    }
+
+    /// Checks if the guard was asleep during a minute past midnight
+    pub fn is_asleep(&self, minute: u32) -> bool {
+        minute < 60 && self.asleep & (1 << minute) != 0
+    }
+
+    pub fn minutes_asleep(&self) -> u32 {
+        self.asleep.count_ones()
+    }
+
+    /// Every minute past midnight the guard was asleep, in order
+    pub fn asleep_minutes(&self) -> impl Iterator<Item = u32> {
+        let asleep = self.asleep;
+
+        (0..60).filter(move |minute| asleep & (1 << minute) != 0)
+    }
+}
+
+impl fmt::Display for Shift {
+    /// A row of the puzzle's timeline, like `11-01  #10    .....####...`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}  #{:<5} ", self.date.format("%m-%d"), self.guard_id)?;
+
+        for minute in 0..60 {
+            f.write_str(if self.is_asleep(minute) { "#" } else { "." })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use day04::{parse_logs, read_logs};
+    use input;
+
+    const SAMPLE: &str = "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+";
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(1518, 11, day).unwrap()
+    }
+
+    #[test]
+    fn from_logs_builds_each_night() {
+        let shifts = Shift::from_logs(&parse_logs(SAMPLE).unwrap()).unwrap();
+
+        let nights: Vec<(u32, NaiveDate, u32)> = shifts
+            .iter()
+            .map(|s| (s.guard_id, s.date, s.minutes_asleep()))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (10, date(1), 45),
+                (99, date(2), 10),
+                (10, date(3), 5),
+                (99, date(4), 10),
+                (99, date(5), 10),
+            ],
+            nights
+        );
+    }
+
+    #[test]
+    fn display_matches_puzzle_timeline() {
+        let shifts = Shift::from_logs(&parse_logs(SAMPLE).unwrap()).unwrap();
+
+        assert_eq!(
+            "11-01  #10    .....####################.....#########################.....",
+            shifts[0].to_string()
+        );
+        assert_eq!(
+            "11-02  #99    ........................................##########..........",
+            shifts[1].to_string()
+        );
+    }
+
+    #[test]
+    fn asleep_minutes_are_in_order() {
+        let shifts = Shift::from_logs(&parse_logs(SAMPLE).unwrap()).unwrap();
+        let minutes: Vec<u32> = shifts[2].asleep_minutes().collect();
+
+        assert_eq!(vec![24, 25, 26, 27, 28], minutes);
+        assert!(shifts[2].is_asleep(24));
+        assert!(!shifts[2].is_asleep(29));
+        assert!(!shifts[2].is_asleep(60));
+    }
+
+    #[test]
+    fn sleeping_at_end_of_shift_lasts_the_hour() {
+        let logs = parse_logs(
+            "[1518-11-01 23:50] Guard #7 begins shift\n\
+             [1518-11-02 00:58] falls asleep\n",
+        )
+        .unwrap();
+        let shifts = Shift::from_logs(&logs).unwrap();
+
+        assert_eq!(vec![58, 59], shifts[0].asleep_minutes().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn shifts_give_part1_answer() {
+        let shifts = Shift::from_logs(&read_logs(input::path(4)).unwrap()).unwrap();
+
+        let by_guard = Shift::by_guard(&shifts);
+        let (guard_id, nights) = by_guard
+            .iter()
+            .max_by_key(|(_, nights)| nights.iter().map(|s| s.minutes_asleep()).sum::<u32>())
+            .unwrap();
+        let minute = (0..60)
+            .max_by_key(|m| nights.iter().filter(|s| s.is_asleep(*m)).count())
+            .unwrap();
+
+        assert_eq!(77084, guard_id * minute);
+    }
+
+    #[test]
+    fn from_logs_needs_a_shift_first() {
+        let logs: Vec<GuardLog> = vec!["[1518-11-01 00:05] falls asleep".parse().unwrap()];
+
+        assert!(Shift::from_logs(&logs).is_err());
+    }
+
+    #[test]
+    fn from_logs_names_an_early_wake() {
+        let logs: Vec<GuardLog> = vec!["[1518-11-01 00:05] wakes up".parse().unwrap()];
+        let message = Shift::from_logs(&logs).unwrap_err().to_string();
+
+        assert!(
+            message.contains("wakes up at 1518-11-01 00:05"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn by_guard_keeps_each_guards_shifts_in_order() {
+        let shifts = Shift::from_logs(&parse_logs(SAMPLE).unwrap()).unwrap();
+        let by_guard = Shift::by_guard(&shifts);
+        let dates = |id| by_guard[&id].iter().map(|s| s.date).collect::<Vec<_>>();
+
+        assert_eq!(2, by_guard.len());
+        assert_eq!(vec![date(1), date(3)], dates(10));
+        assert_eq!(vec![date(2), date(4), date(5)], dates(99));
+    }
+}
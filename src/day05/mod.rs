@@ -1,114 +1,170 @@
-use std::error::Error;
-use std::fs::File;
-use std::io::prelude::*;
-
-pub fn read_file(filename: &str) -> Result<Vec<char>, Box<dyn Error>> {
-    let f = File::open(filename)?;
-
-    let result: Vec<char> = f.bytes().map(|b| b.unwrap() as char).collect();
-
-    Ok(result)
-}
-
-pub fn compress_polarities(chars: &Vec<char>) -> Vec<char> {
-    // use a stack
-    let mut result: Vec<char> = vec![];
-
-    for c in chars.iter() {
-        if result.len() == 0 {
-            result.push(*c);
-        } else if is_polar_pair(result[result.len() - 1], *c) {
-            result.pop(); // remove it!
-        } else {
-            result.push(*c);
-        }
-    }
-
-    result
-}
-
-fn is_polar_pair(c1: char, c2: char) -> bool {
-    let byte1 = c1 as i8;
-    let byte2 = c2 as i8;
-    let diff = (byte1 - byte2).abs();
-    return diff == 32;
-}
-
-pub fn find_shortest(chars: &Vec<char>) -> (char, usize) {
-    let mut shortest_unit: Option<char> = None;
-    let mut shortest_size: Option<usize> = None;
-
-    let a = 'a' as u8;
-    let z = 'z' as u8;
-
-    for unit in a..z {
-        let working_data = clone_without(&chars, unit as char);
-        let result = compress_polarities(&working_data);
-
-        if shortest_size.is_none() {
-            shortest_size = Some(result.len());
-            shortest_unit = Some(unit as char);
-        } else if result.len() < shortest_size.unwrap() {
-            shortest_size = Some(result.len());
-            shortest_unit = Some(unit as char);
-        }
-    }
-
-    (shortest_unit.unwrap(), shortest_size.unwrap())
-}
-
-fn clone_without(chars: &Vec<char>, unit: char) -> Vec<char> {
-    let mut result = vec![];
-
-    for c in chars.iter() {
-        if !is_unit(unit, *c) {
-            result.push(*c);
-        }
-    }
-
-    result
-}
-
-fn is_unit(unit: char, c: char) -> bool {
-    return c == unit || (c as i8 - unit as i8).abs() == 32;
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn read_file_gets_whole_string() {
-        let chars = read_file("inputs\\day05.txt").expect("Error read_file");
-        assert_eq!(50000, chars.len());
-    }
-
-    #[test]
-    fn compress_polarities_sample() {
-        let sample: Vec<char> = "dabAcCaCBAcCcaDA".chars().collect();
-        let result = compress_polarities(&sample);
-        assert_eq!(10, result.len());
-    }
-
-    #[test]
-    fn compress_polarities_input() {
-        let input = read_file("inputs\\day05.txt").unwrap();
-        let result = compress_polarities(&input);
-        assert_eq!(11152, result.len());
-    }
-
-    #[test]
-    fn find_shortest_sample() {
-        let sample: Vec<char> = "dabAcCaCBAcCcaDA".chars().collect();
-        let (unit, length) = find_shortest(&sample);
-        assert_eq!('c', unit);
-        assert_eq!(4, length);
-    }
-
-    #[test]
-    fn find_shortest_input() {
-        let input = read_file("inputs\\day05.txt").unwrap();
-        let (_, length) = find_shortest(&input);
-        assert_eq!(6136, length);
-    }
-}
+use std::io::BufRead;
+use std::path::Path;
+
+use error::{Error, Result};
+use input;
+use solver::Solver;
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    type Input = Vec<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Alchemical Reduction";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_polymer(input)
+    }
+
+    fn part1(&self, polymer: &Self::Input) -> Result<Self::Part1> {
+        Ok(compress_polarities(polymer).len())
+    }
+
+    fn part2(&self, polymer: &Self::Input) -> Result<Self::Part2> {
+        let (_, length) = find_shortest(polymer);
+        Ok(length)
+    }
+}
+
+pub fn read_file<P: AsRef<Path>>(filename: P) -> Result<Vec<char>> {
+    input::parse_file(filename, read_from)
+}
+
+/// Reads a polymer from anything buffered, like an open file or stdin
+pub fn read_from<R: BufRead>(reader: R) -> Result<Vec<char>> {
+    parse_polymer(&input::read_from(reader)?)
+}
+
+/// Reads a polymer, which has to be a single line of ASCII letters
+pub fn parse_polymer(input: &str) -> Result<Vec<char>> {
+    let polymer = input.trim();
+
+    for (index, c) in polymer.chars().enumerate() {
+        if !c.is_ascii_alphabetic() {
+            return Err(Error::parse(
+                1,
+                index + 1,
+                format!("'{}' is not a polymer unit", c),
+            ));
+        }
+    }
+
+    Ok(polymer.chars().collect())
+}
+
+pub fn compress_polarities(chars: &Vec<char>) -> Vec<char> {
+    // use a stack
+    let mut result: Vec<char> = vec![];
+
+    for c in chars.iter() {
+        if result.len() == 0 {
+            result.push(*c);
+        } else if is_polar_pair(result[result.len() - 1], *c) {
+            result.pop(); // remove it!
+        } else {
+            result.push(*c);
+        }
+    }
+
+    result
+}
+
+fn is_polar_pair(c1: char, c2: char) -> bool {
+    let byte1 = c1 as i8;
+    let byte2 = c2 as i8;
+    let diff = (byte1 - byte2).abs();
+    return diff == 32;
+}
+
+pub fn find_shortest(chars: &Vec<char>) -> (char, usize) {
+    let mut shortest_unit: Option<char> = None;
+    let mut shortest_size: Option<usize> = None;
+
+    let a = 'a' as u8;
+    let z = 'z' as u8;
+
+    for unit in a..z {
+        let working_data = clone_without(&chars, unit as char);
+        let result = compress_polarities(&working_data);
+
+        if shortest_size.is_none() {
+            shortest_size = Some(result.len());
+            shortest_unit = Some(unit as char);
+        } else if result.len() < shortest_size.unwrap() {
+            shortest_size = Some(result.len());
+            shortest_unit = Some(unit as char);
+        }
+    }
+
+    (shortest_unit.unwrap(), shortest_size.unwrap())
+}
+
+fn clone_without(chars: &Vec<char>, unit: char) -> Vec<char> {
+    let mut result = vec![];
+
+    for c in chars.iter() {
+        if !is_unit(unit, *c) {
+            result.push(*c);
+        }
+    }
+
+    result
+}
+
+fn is_unit(unit: char, c: char) -> bool {
+    return c == unit || (c as i8 - unit as i8).abs() == 32;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use input;
+
+    #[test]
+    fn read_file_gets_whole_string() {
+        let chars = read_file(input::path(5)).expect("Error read_file");
+        assert_eq!(50000, chars.len());
+    }
+
+    #[test]
+    fn compress_polarities_sample() {
+        let sample: Vec<char> = "dabAcCaCBAcCcaDA".chars().collect();
+        let result = compress_polarities(&sample);
+        assert_eq!(10, result.len());
+    }
+
+    #[test]
+    fn compress_polarities_input() {
+        let input = read_file(input::path(5)).unwrap();
+        let result = compress_polarities(&input);
+        assert_eq!(11152, result.len());
+    }
+
+    #[test]
+    fn find_shortest_sample() {
+        let sample: Vec<char> = "dabAcCaCBAcCcaDA".chars().collect();
+        let (unit, length) = find_shortest(&sample);
+        assert_eq!('c', unit);
+        assert_eq!(4, length);
+    }
+
+    #[test]
+    fn find_shortest_input() {
+        let input = read_file(input::path(5)).unwrap();
+        let (_, length) = find_shortest(&input);
+        assert_eq!(6136, length);
+    }
+
+    #[test]
+    fn parse_polymer_reports_column_of_bad_unit() {
+        let err = parse_polymer("dabA cC\n").unwrap_err();
+
+        assert_eq!(
+            "line 1, column 5: ' ' is not a polymer unit",
+            err.to_string()
+        );
+    }
+}
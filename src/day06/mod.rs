@@ -1,200 +1,249 @@
-use std::collections::HashMap;
-use std::collections::HashSet;
-use std::error::Error;
-use std::fs;
-
-type Point = (i32, i32);
-
-pub fn find_max_enclosed_area(points: &[Point]) -> (Point, usize) {
-    let maxx = points.iter().map(|(x, _)| x).max().unwrap();
-    let maxy = points.iter().map(|(_, y)| y).max().unwrap();
-
-    let maxx = *maxx as usize + 1;
-    let maxy = *maxy as usize + 1;
-    // println!("maxx= {}, maxy={}", maxx, maxy);
-
-    let mut grid: Vec<Vec<Option<usize>>> = vec![vec![None; maxy]; maxx];
-
-    for x in 0..grid.len() {
-        for y in 0..grid[x].len() {
-            grid[x][y] = find_closest(&points, x, y);
-        }
-    }
-
-    let mut infinite_indexes = HashSet::new();
-
-    for x in 0..maxx {
-        if let Some(index) = grid[x][0] {
-            infinite_indexes.insert(index);
-        }
-
-        if let Some(index) = grid[x][maxy - 1] {
-            infinite_indexes.insert(index);
-        }
-    }
-
-    for y in 0..maxy {
-        if let Some(index) = grid[0][y] {
-            infinite_indexes.insert(index);
-        }
-
-        if let Some(index) = grid[maxx - 1][y] {
-            infinite_indexes.insert(index);
-        }
-    }
-
-    let mut finite_sizes = HashMap::new();
-
-    for i in 0..points.len() {
-        if !infinite_indexes.contains(&(i as usize)) {
-            let count = finite_sizes.entry(i).or_insert(0);
-            *count += count_indexes(&grid, i);;
-        }
-    }
-
-    let mut min = (10000, 0);
-    for (index, count) in finite_sizes.iter() {
-        // println!("{} = {}", (97u8 + *index as u8) as char, count);
-        let (_, max_count) = min;
-
-        if *count > max_count {
-            min = (*index, *count);
-        }
-    }
-
-    // for y in 0..maxy {
-    //     for x in 0..maxx {
-    //         match grid[x][y] {
-    //             Some(i) => print!("{}", (97u8 + i as u8) as char),
-    //             None => print!(".")
-    //         }
-    //     }
-    //     println!();
-    // }
-
-    let (index, count) = min;
-    (points[index], count)
-}
-
-pub fn find_area_of_min_region(points: &Vec<Point>, max_distance: usize) -> usize {
-    let maxx = points.iter().map(|(x, _)| x).max().unwrap();
-    let maxy = points.iter().map(|(_, y)| y).max().unwrap();
-
-    let maxx = *maxx as usize + 1;
-    let maxy = *maxy as usize + 1;
-    // println!("maxx= {}, maxy={}", maxx, maxy);
-
-    let mut count = 0;
-    for x in 0..maxx {
-        for y in 0..maxy {
-            let total_distance = find_total_manhattan_distance(&points, &(x as i32, y as i32));
-            if total_distance < max_distance {
-                count += 1;
-            }
-        }
-    }
-
-    count
-}
-
-fn find_total_manhattan_distance(points: &Vec<Point>, point: &Point) -> usize {
-    points.iter().map(|p| manhattan_distance(p, point)).sum()
-}
-
-fn count_indexes(grid: &Vec<Vec<Option<usize>>>, index: usize) -> usize {
-    let mut count = 0;
-
-    for x in 0..grid.len() {
-        for y in 0..grid[x].len() {
-            if grid[x][y] == Some(index) {
-                count += 1;
-            }
-        }
-    }
-
-    count
-}
-
-fn manhattan_distance(p1: &Point, p2: &Point) -> usize {
-    let (x1, y1) = p1;
-    let (x2, y2) = p2;
-    ((x2 - x1).abs() + (y2 - y1).abs()) as usize
-}
-
-fn find_closest(points: &[Point], x: usize, y: usize) -> Option<usize> {
-    let mut closest_index = 0;
-    let mut shortest_distance = 10000000;
-    let mut is_same = false;
-
-    for i in 0..points.len() {
-        let distance = manhattan_distance(&points[i], &(x as i32, y as i32));
-
-        if distance < shortest_distance {
-            shortest_distance = distance;
-            is_same = false;
-            closest_index = i;
-        } else if distance == shortest_distance {
-            is_same = true;
-        }
-    }
-
-    if is_same {
-        None
-    } else {
-        Some(closest_index)
-    }
-}
-
-pub fn read_input(filename: &str) -> Result<Vec<Point>, Box<dyn Error>> {
-    let s = fs::read_to_string(filename)?;
-    let mut result = vec![];
-
-    for line in s.lines() {
-        let parts = line.split(", ").collect::<Vec<&str>>();
-        let x = parts[0].parse().unwrap();
-        let y = parts[1].parse().unwrap();
-        result.push((x, y));
-    }
-
-    Ok(result)
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    fn sample() -> Vec<Point> {
-        vec![(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]
-    }
-
-    #[test]
-    fn find_max_enclosed_area_sample() {
-        let points = sample();
-        let (p, count) = find_max_enclosed_area(&points);
-
-        assert_eq!((5, 5), p);
-        assert_eq!(17, count);
-    }
-
-    #[test]
-    fn find_max_enclosed_area_input() {
-        let points = read_input("inputs\\day06.txt").unwrap();
-        let (_, count) = find_max_enclosed_area(&points);
-
-        assert_eq!(2917, count);
-    }
-
-    #[test]
-    fn find_max_region_size_sample() {
-        let points = sample();
-        let size = find_area_of_min_region(&points, 32);
-        assert_eq!(16, size);
-    }
-
-    #[test]
-    fn find_max_region_size_input() {
-        let points = read_input("inputs\\day06.txt").unwrap();
-        let size = find_area_of_min_region(&points, 10_000);
-        assert_eq!(44202, size);
-    }
-}
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::path::Path;
+
+use error::{Error, Result};
+use geometry::{Point, Rect};
+use grid::Grid;
+use input;
+use solver::Solver;
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    type Input = Vec<Point>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Chronal Coordinates";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, points: &Self::Input) -> Result<Self::Part1> {
+        let (_, size) = find_max_enclosed_area(points);
+        Ok(size)
+    }
+
+    fn part2(&self, points: &Self::Input) -> Result<Self::Part2> {
+        Ok(find_area_of_min_region(points, 10_000))
+    }
+}
+
+pub fn find_max_enclosed_area(points: &[Point]) -> (Point, usize) {
+    let (width, height) = grid_size(points);
+    let grid = Grid::from_fn(width, height, |x, y| find_closest(points, x, y));
+
+    // any area reaching the edge of the grid goes on forever
+    let edges = grid
+        .row(0)
+        .into_iter()
+        .chain(grid.row(height - 1))
+        .flat_map(|row| row.iter())
+        .chain(grid.column(0))
+        .chain(grid.column(width - 1));
+
+    let infinite_indexes: HashSet<usize> = edges.filter_map(|closest| *closest).collect();
+
+    let mut finite_sizes = HashMap::new();
+
+    for i in 0..points.len() {
+        if !infinite_indexes.contains(&i) {
+            let count = finite_sizes.entry(i).or_insert(0);
+            *count += count_indexes(&grid, i);
+        }
+    }
+
+    let mut min = (10000, 0);
+    for (index, count) in finite_sizes.iter() {
+        let (_, max_count) = min;
+
+        if *count > max_count {
+            min = (*index, *count);
+        }
+    }
+
+    let (index, count) = min;
+    (points[index], count)
+}
+
+pub fn find_area_of_min_region(points: &[Point], max_distance: usize) -> usize {
+    let (width, height) = grid_size(points);
+    let grid = Grid::from_fn(width, height, |x, y| {
+        find_total_manhattan_distance(points, &Point::new(x as i32, y as i32))
+    });
+
+    grid.cells()
+        .iter()
+        .filter(|total_distance| **total_distance < max_distance)
+        .count()
+}
+
+/// The size of a grid reaching from the origin to just past the furthest
+/// point on each axis
+fn grid_size(points: &[Point]) -> (usize, usize) {
+    match Rect::bounding_box(points.iter().cloned()) {
+        Some(bounds) => (bounds.right() as usize, bounds.bottom() as usize),
+        None => (0, 0),
+    }
+}
+
+fn find_total_manhattan_distance(points: &[Point], point: &Point) -> usize {
+    points
+        .iter()
+        .map(|p| p.manhattan_distance(point) as usize)
+        .sum()
+}
+
+fn count_indexes(grid: &Grid<Option<usize>>, index: usize) -> usize {
+    grid.cells()
+        .iter()
+        .filter(|closest| **closest == Some(index))
+        .count()
+}
+
+fn find_closest(points: &[Point], x: usize, y: usize) -> Option<usize> {
+    let mut closest_index = 0;
+    let mut shortest_distance = 10000000;
+    let mut is_same = false;
+
+    let here = Point::new(x as i32, y as i32);
+
+    for (i, point) in points.iter().enumerate() {
+        let distance = point.manhattan_distance(&here);
+
+        if distance < shortest_distance {
+            shortest_distance = distance;
+            is_same = false;
+            closest_index = i;
+        } else if distance == shortest_distance {
+            is_same = true;
+        }
+    }
+
+    if is_same {
+        None
+    } else {
+        Some(closest_index)
+    }
+}
+
+pub fn read_input<P: AsRef<Path>>(filename: P) -> Result<Vec<Point>> {
+    input::parse_file(filename, read_from)
+}
+
+/// Reads points from anything buffered, like an open file or stdin
+pub fn read_from<R: BufRead>(reader: R) -> Result<Vec<Point>> {
+    parse_input(&input::read_from(reader)?)
+}
+
+/// Reads one `x, y` point per line. Coordinates can't be negative, and there
+/// has to be at least one point.
+pub fn parse_input(input: &str) -> Result<Vec<Point>> {
+    let mut result = vec![];
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let line_number = index + 1;
+        let comma = line.find(',').ok_or_else(|| {
+            Error::parse(
+                line_number,
+                1,
+                format!("Expected 'x, y' but got '{}'", line),
+            )
+        })?;
+
+        let x = parse_coordinate(&line[..comma], line_number, 1)?;
+        let y = parse_coordinate(&line[comma + 1..], line_number, comma + 2)?;
+        result.push(Point::new(x, y));
+    }
+
+    if result.is_empty() {
+        return Err(Error::domain("There are no coordinates in the input"));
+    }
+
+    Ok(result)
+}
+
+fn parse_coordinate(token: &str, line: usize, column: usize) -> Result<i32> {
+    let column = column + token.len() - token.trim_start().len();
+    let token = token.trim();
+
+    match token.parse::<i32>() {
+        Ok(value) if value >= 0 => Ok(value),
+        Ok(_) => Err(Error::parse(
+            line,
+            column,
+            format!("Coordinate '{}' is negative", token),
+        )),
+        Err(err) => Err(Error::parse(
+            line,
+            column,
+            format!("Can't parse '{}' as a coordinate: {}", token, err),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use input;
+
+    fn sample() -> Vec<Point> {
+        read_from("1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9\n".as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn find_max_enclosed_area_sample() {
+        let points = sample();
+        let (p, count) = find_max_enclosed_area(&points);
+
+        assert_eq!(Point::new(5, 5), p);
+        assert_eq!(17, count);
+    }
+
+    #[test]
+    fn find_max_enclosed_area_input() {
+        let points = read_input(input::path(6)).unwrap();
+        let (_, count) = find_max_enclosed_area(&points);
+
+        assert_eq!(2917, count);
+    }
+
+    #[test]
+    fn find_max_region_size_sample() {
+        let points = sample();
+        let size = find_area_of_min_region(&points, 32);
+        assert_eq!(16, size);
+    }
+
+    #[test]
+    fn find_max_region_size_input() {
+        let points = read_input(input::path(6)).unwrap();
+        let size = find_area_of_min_region(&points, 10_000);
+        assert_eq!(44202, size);
+    }
+
+    #[test]
+    fn parse_input_reports_column_of_bad_coordinate() {
+        let err = parse_input("1, 1\n1, six\n").unwrap_err();
+
+        assert!(err.to_string().starts_with("line 2, column 4:"));
+    }
+
+    #[test]
+    fn parse_input_rejects_negative_coordinates() {
+        assert!(parse_input("-1, 1\n").is_err());
+    }
+
+    #[test]
+    fn parse_input_needs_a_point() {
+        assert!(parse_input("\n").is_err());
+    }
+}
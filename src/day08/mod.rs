@@ -1,127 +1,223 @@
-use std::fs;
-
-pub fn metadata_checksum(license: &str) -> usize {
-    let tree = get_tree_from_str(license);
-    sum_metadata(&tree)
-}
-
-pub fn read_file(filename: &str) -> String {
-    return fs::read_to_string(filename).unwrap();
-}
-
-fn get_tree_from_str(license: &str) -> LicenseNode {
-    let license: Vec<usize> = license
-        .split(' ')
-        .map(|c| c.parse::<usize>().unwrap())
-        .collect();
-
-    create_tree(&license)
-}
-
-struct LicenseNode {
-    metadata: Vec<usize>,
-    children: Vec<Box<LicenseNode>>,
-}
-
-fn create_tree(license: &[usize]) -> LicenseNode {
-    let mut index = 0;
-    get_node(license, &mut index)
-}
-
-fn get_node(license: &[usize], index: &mut usize) -> LicenseNode {
-    // println!("reading node starting at {}", index);
-
-    let child_count = license[*index];
-    *index += 1;
-
-    let metadata_count = license[*index];
-    *index += 1;
-
-    // println!("reading {} children", child_count);
-    let mut children = vec![];
-    for _ in 0..child_count {
-        children.push(Box::new(get_node(license, index)));
-    }
-
-    // println!("reading {} metadata fields", metadata_count);
-    let mut metadata = vec![];
-    for _ in 0..metadata_count {
-        metadata.push(license[*index]);
-        *index += 1;
-    }
-
-    // println!("metadata={:?} children count={}", metadata, children.len());
-
-    LicenseNode { metadata, children }
-}
-
-fn sum_metadata(root: &LicenseNode) -> usize {
-    let mut sum = 0;
-
-    for child in root.children.iter() {
-        sum += sum_metadata(&child);
-    }
-
-    let sum_metadata: usize = root.metadata.iter().sum();
-
-    sum + sum_metadata
-}
-
-pub fn calculate_node_value(license: &str) -> usize {
-    let tree = get_tree_from_str(license);
-    node_value(&tree)
-}
-
-fn node_value(node: &LicenseNode) -> usize {
-    // if a node has no child nodes, its value is the sum of its metadata
-    if node.children.len() == 0 {
-        return node.metadata.iter().sum();
-    }
-
-    let mut value = 0;
-    // if a child has children, metadata is indexes int the child list
-    for index in node.metadata.iter() {
-        // invalid indexes are skipped
-        if *index == 0 || *index > node.children.len() {
-            continue;
-        }
-
-        value += node_value(&node.children[index - 1]);
-    }
-
-    value
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use spectral::prelude::*;
-
-    #[test]
-    fn metadata_checksum_sample() {
-        let input = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
-        let checksum = metadata_checksum(input);
-        assert_that!(checksum).is_equal_to(138);
-    }
-
-    #[test]
-    fn metadata_checksum_input() {
-        let input = read_file("inputs\\day08.txt");
-        let checksum = metadata_checksum(&input);
-        assert_that!(checksum).is_equal_to(45194);
-    }
-
-    #[test]
-    fn node_value_sample() {
-        let input = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
-        let value = calculate_node_value(input);
-        assert_that!(value).is_equal_to(66);
-    }
-
-    #[test]
-    fn node_value_input() {
-        let input = read_file("inputs\\day08.txt");
-        let value = calculate_node_value(&input);
-        assert_that!(value).is_equal_to(22989);
-    }
-}
+use std::io::BufRead;
+use std::path::Path;
+
+use error::{Error, Result};
+use input;
+use solver::Solver;
+
+pub struct Day08;
+
+impl Solver for Day08 {
+    type Input = LicenseNode;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Memory Maneuver";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_license(input)
+    }
+
+    fn part1(&self, tree: &Self::Input) -> Result<Self::Part1> {
+        Ok(sum_metadata(tree))
+    }
+
+    fn part2(&self, tree: &Self::Input) -> Result<Self::Part2> {
+        Ok(node_value(tree))
+    }
+}
+
+pub fn metadata_checksum(license: &str) -> Result<usize> {
+    let tree = parse_license(license)?;
+    Ok(sum_metadata(&tree))
+}
+
+pub fn read_file<P: AsRef<Path>>(filename: P) -> Result<String> {
+    input::read_file(filename)
+}
+
+pub fn read_license<P: AsRef<Path>>(filename: P) -> Result<LicenseNode> {
+    input::parse_file(filename, read_from)
+}
+
+/// Reads a license tree from anything buffered, like an open file or stdin
+pub fn read_from<R: BufRead>(reader: R) -> Result<LicenseNode> {
+    parse_license(&input::read_from(reader)?)
+}
+
+pub struct LicenseNode {
+    metadata: Vec<usize>,
+    children: Vec<Box<LicenseNode>>,
+}
+
+/// Builds the tree of nodes from a license, which is a single line of
+/// numbers separated by spaces
+pub fn parse_license(license: &str) -> Result<LicenseNode> {
+    let mut numbers = vec![];
+
+    for (line_index, line) in license.lines().enumerate() {
+        let mut start = None;
+
+        // the extra space at the end finishes off the last number on the line
+        for (column, c) in line.char_indices().chain(Some((line.len(), ' '))) {
+            match (c.is_whitespace(), start) {
+                (false, None) => start = Some(column),
+                (true, Some(from)) => {
+                    let token = &line[from..column];
+                    let number = token.parse::<usize>().map_err(|err| {
+                        Error::parse(
+                            line_index + 1,
+                            from + 1,
+                            format!("Can't parse '{}' as a number: {}", token, err),
+                        )
+                    })?;
+
+                    numbers.push(number);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut index = 0;
+    let tree = get_node(&numbers, &mut index)?;
+
+    if index < numbers.len() {
+        return Err(Error::domain(format!(
+            "The license has {} numbers left over after the root node",
+            numbers.len() - index
+        )));
+    }
+
+    Ok(tree)
+}
+
+fn get_node(license: &[usize], index: &mut usize) -> Result<LicenseNode> {
+    let child_count = next_number(license, index)?;
+    let metadata_count = next_number(license, index)?;
+
+    let mut children = vec![];
+    for _ in 0..child_count {
+        children.push(Box::new(get_node(license, index)?));
+    }
+
+    let mut metadata = vec![];
+    for _ in 0..metadata_count {
+        metadata.push(next_number(license, index)?);
+    }
+
+    Ok(LicenseNode { metadata, children })
+}
+
+fn next_number(license: &[usize], index: &mut usize) -> Result<usize> {
+    let number = license
+        .get(*index)
+        .ok_or_else(|| Error::domain("The license ends in the middle of a node"))?;
+    *index += 1;
+
+    Ok(*number)
+}
+
+fn sum_metadata(root: &LicenseNode) -> usize {
+    let mut sum = 0;
+
+    for child in root.children.iter() {
+        sum += sum_metadata(child);
+    }
+
+    let sum_metadata: usize = root.metadata.iter().sum();
+
+    sum + sum_metadata
+}
+
+pub fn calculate_node_value(license: &str) -> Result<usize> {
+    let tree = parse_license(license)?;
+    Ok(node_value(&tree))
+}
+
+fn node_value(node: &LicenseNode) -> usize {
+    // if a node has no child nodes, its value is the sum of its metadata
+    if node.children.is_empty() {
+        return node.metadata.iter().sum();
+    }
+
+    let mut value = 0;
+    // if a child has children, metadata is indexes int the child list
+    for index in node.metadata.iter() {
+        // invalid indexes are skipped
+        if *index == 0 || *index > node.children.len() {
+            continue;
+        }
+
+        value += node_value(&node.children[index - 1]);
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use input;
+    use spectral::prelude::*;
+
+    #[test]
+    fn metadata_checksum_sample() {
+        let input = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+        let checksum = metadata_checksum(input).unwrap();
+        assert_that!(checksum).is_equal_to(138);
+    }
+
+    #[test]
+    fn metadata_checksum_input() {
+        let input = read_file(input::path(8)).unwrap();
+        let checksum = metadata_checksum(&input).unwrap();
+        assert_that!(checksum).is_equal_to(45194);
+    }
+
+    #[test]
+    fn node_value_sample() {
+        let input = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+        let value = calculate_node_value(input).unwrap();
+        assert_that!(value).is_equal_to(66);
+    }
+
+    #[test]
+    fn read_from_builds_tree() {
+        let tree = read_from("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2\n".as_bytes()).unwrap();
+        assert_that!(node_value(&tree)).is_equal_to(66);
+    }
+
+    #[test]
+    fn read_license_input() {
+        let tree = read_license(input::path(8)).unwrap();
+        assert_that!(sum_metadata(&tree)).is_equal_to(45194);
+    }
+
+    #[test]
+    fn node_value_input() {
+        let input = read_file(input::path(8)).unwrap();
+        let value = calculate_node_value(&input).unwrap();
+        assert_that!(value).is_equal_to(22989);
+    }
+
+    #[test]
+    fn parse_license_reports_column_of_bad_number() {
+        let err = parse_license("2 3 0 x 10").err().unwrap();
+        assert_that!(err.to_string()).is_equal_to(String::from(
+            "line 1, column 7: Can't parse 'x' as a number: invalid digit found in string",
+        ));
+    }
+
+    #[test]
+    fn parse_license_rejects_truncated_tree() {
+        assert_that!(parse_license("2 3 0 3 10 11").is_err()).is_true();
+    }
+
+    #[test]
+    fn parse_license_rejects_leftover_numbers() {
+        assert_that!(parse_license("0 1 99 4").is_err()).is_true();
+    }
+}
@@ -1,76 +1,179 @@
-use std::collections::VecDeque;
-
-pub fn winning_score(players: usize, last_marble: u32) -> u32 {
-    let mut queue = VecDeque::new();
-    let mut scores = vec![0; players];
-
-    queue.push_back(0);
-
-    for i in 1..last_marble + 1 {
-        if i % 23 == 0 {
-            rotate_right(&mut queue, 7);
-            let value = queue.pop_back().unwrap();
-            scores[(i as usize % players) as usize] += i + value;
-            rotate_left(&mut queue, 1);
-        } else {
-            rotate_left(&mut queue, 1);
-            queue.push_back(i);
-        }
-    }
-
-    *scores.iter().max().unwrap()
-}
-
-fn rotate_right(queue: &mut VecDeque<u32>, count: usize) {
-    for _ in 0..count {
-        let value = queue.pop_back().unwrap();
-        queue.push_front(value);
-    }
-}
-
-fn rotate_left(queue: &mut VecDeque<u32>, count: usize) {
-    for _ in 0..count {
-        let value = queue.pop_front().unwrap();
-        queue.push_back(value);
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use spectral::prelude::*;
-
-    macro_rules! winning_score_tests {
-        ($($name:ident: $value:expr,)*) => {
-            $(
-                #[test]
-                fn $name() {
-                    let (players, last_marble, expected) = $value;
-                    let result = winning_score(players, last_marble);
-                    assert_that!(result).is_equal_to(expected);
-                }
-            )*
-        }
-    }
-
-    winning_score_tests! {
-        winning_score_9: (9, 25, 32),
-        winning_score_10: (10, 1618, 8317),
-        winning_score_13: (13, 7999, 146373),
-        winning_score_17: (17, 1104, 2764),
-        winning_score_21: (21, 6111, 54718),
-        winning_score_30: (30, 5807, 37305),
-    }
-
-    #[test]
-    fn winning_score_input() {
-        let score = winning_score(486, 70833);
-        assert_that!(score).is_equal_to(373597);
-    }
-
-    #[test]
-    fn winning_score_input_larger() {
-        let score = winning_score(486, 70833 * 100);
-        assert_that!(score).is_equal_to(2954067253);
-    }
-}
+use regex::Regex;
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::path::Path;
+
+use error::{Error, Result};
+use input;
+use solver::Solver;
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    type Input = (usize, u32);
+    type Part1 = u32;
+    type Part2 = u32;
+
+    const DAY: u32 = 9;
+    const TITLE: &'static str = "Marble Mania";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, game: &Self::Input) -> Result<Self::Part1> {
+        let (players, last_marble) = *game;
+        Ok(winning_score(players, last_marble))
+    }
+
+    fn part2(&self, game: &Self::Input) -> Result<Self::Part2> {
+        let (players, last_marble) = *game;
+        let last_marble = last_marble
+            .checked_mul(100)
+            .ok_or_else(|| Error::domain("The last marble is too large to play 100 times over"))?;
+        Ok(winning_score(players, last_marble))
+    }
+}
+
+pub fn read_input<P: AsRef<Path>>(filename: P) -> Result<(usize, u32)> {
+    input::parse_file(filename, read_from)
+}
+
+/// Reads the game from anything buffered, like an open file or stdin
+pub fn read_from<R: BufRead>(reader: R) -> Result<(usize, u32)> {
+    parse_input(&input::read_from(reader)?)
+}
+
+/// Reads the player count and the value of the last marble from a line like
+/// "486 players; last marble is worth 70833 points"
+pub fn parse_input(input: &str) -> Result<(usize, u32)> {
+    lazy_static! {
+        static ref RE: Regex =
+            Regex::new("^(\\d+) players; last marble is worth (\\d+) points$").unwrap();
+    }
+
+    let line = input.trim();
+    let capture = RE
+        .captures(line)
+        .ok_or_else(|| Error::parse(1, 1, format!("Can't parse '{}' as a marble game", line)))?;
+
+    let number = |group: usize| {
+        let m = capture.get(group).unwrap();
+        m.as_str().parse::<u32>().map_err(|err| {
+            Error::parse(
+                1,
+                m.start() + 1,
+                format!("Can't parse '{}' as a number: {}", m.as_str(), err),
+            )
+        })
+    };
+
+    let players = number(1)?;
+    let last_marble = number(2)?;
+
+    if players == 0 {
+        return Err(Error::domain("A marble game needs at least one player"));
+    }
+
+    Ok((players as usize, last_marble))
+}
+
+pub fn winning_score(players: usize, last_marble: u32) -> u32 {
+    let mut queue = VecDeque::new();
+    let mut scores = vec![0; players];
+
+    queue.push_back(0);
+
+    for i in 1..last_marble + 1 {
+        if i % 23 == 0 {
+            rotate_right(&mut queue, 7);
+            let value = queue.pop_back().unwrap();
+            scores[(i as usize % players) as usize] += i + value;
+            rotate_left(&mut queue, 1);
+        } else {
+            rotate_left(&mut queue, 1);
+            queue.push_back(i);
+        }
+    }
+
+    *scores.iter().max().unwrap()
+}
+
+fn rotate_right(queue: &mut VecDeque<u32>, count: usize) {
+    for _ in 0..count {
+        let value = queue.pop_back().unwrap();
+        queue.push_front(value);
+    }
+}
+
+fn rotate_left(queue: &mut VecDeque<u32>, count: usize) {
+    for _ in 0..count {
+        let value = queue.pop_front().unwrap();
+        queue.push_back(value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use input;
+    use spectral::prelude::*;
+
+    macro_rules! winning_score_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (players, last_marble, expected) = $value;
+                    let result = winning_score(players, last_marble);
+                    assert_that!(result).is_equal_to(expected);
+                }
+            )*
+        }
+    }
+
+    winning_score_tests! {
+        winning_score_9: (9, 25, 32),
+        winning_score_10: (10, 1618, 8317),
+        winning_score_13: (13, 7999, 146373),
+        winning_score_17: (17, 1104, 2764),
+        winning_score_21: (21, 6111, 54718),
+        winning_score_30: (30, 5807, 37305),
+    }
+
+    #[test]
+    fn read_input_reads_file() {
+        let game = read_input(input::path(9)).unwrap();
+        assert_that!(game).is_equal_to((486, 70833));
+    }
+
+    #[test]
+    fn parse_input_reads_game() {
+        let game = parse_input("10 players; last marble is worth 1618 points\n").unwrap();
+        assert_that!(game).is_equal_to((10, 1618));
+    }
+
+    #[test]
+    fn parse_input_rejects_garbage() {
+        let game = parse_input("ten players");
+        assert!(game.is_err());
+    }
+
+    #[test]
+    fn parse_input_needs_players() {
+        let game = parse_input("0 players; last marble is worth 25 points");
+        assert!(game.is_err());
+    }
+
+    #[test]
+    fn winning_score_input() {
+        let score = winning_score(486, 70833);
+        assert_that!(score).is_equal_to(373597);
+    }
+
+    #[test]
+    fn winning_score_input_larger() {
+        let score = winning_score(486, 70833 * 100);
+        assert_that!(score).is_equal_to(2954067253);
+    }
+}
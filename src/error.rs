@@ -0,0 +1,150 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::result;
+
+/// Everything that can go wrong reading and solving a puzzle
+#[derive(Debug)]
+pub enum Error {
+    /// A file could not be read
+    Io {
+        path: Option<PathBuf>,
+        err: io::Error,
+    },
+
+    /// There's no input file for a day
+    MissingInput { day: u32, path: PathBuf },
+
+    /// The input is not in the expected format. Lines and columns start at 1.
+    Parse {
+        file: Option<PathBuf>,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    /// The input is well formed but the puzzle has no answer for it
+    Domain(String),
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+impl Error {
+    pub fn parse<S: Into<String>>(line: usize, column: usize, message: S) -> Error {
+        Error::Parse {
+            file: None,
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn domain<S: Into<String>>(message: S) -> Error {
+        Error::Domain(message.into())
+    }
+
+    /// Records which file the error came from, if it's not already known
+    pub fn in_file<P: AsRef<Path>>(self, path: P) -> Error {
+        match self {
+            Error::Parse {
+                file: None,
+                line,
+                column,
+                message,
+            } => Error::Parse {
+                file: Some(path.as_ref().to_path_buf()),
+                line,
+                column,
+                message,
+            },
+            Error::Io { path: None, err } => Error::Io {
+                path: Some(path.as_ref().to_path_buf()),
+                err,
+            },
+            other => other,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io { path: None, err }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io {
+                path: Some(path),
+                err,
+            } => write!(f, "Could not read '{}': {}", path.display(), err),
+            Error::Io { path: None, err } => write!(f, "{}", err),
+            Error::MissingInput { day, path } => write!(
+                f,
+                "No input for day {}: '{}' does not exist (set {} or pass --inputs <dir>)",
+                day,
+                path.display(),
+                ::input::INPUT_DIR_VAR
+            ),
+            Error::Parse {
+                file: Some(file),
+                line,
+                column,
+                message,
+            } => write!(f, "{}:{}:{}: {}", file.display(), line, column, message),
+            Error::Parse {
+                file: None,
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            Error::Domain(message) => f.write_str(message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io { err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_error_shows_position() {
+        let err = Error::parse(3, 7, "expected a number");
+
+        assert_eq!("line 3, column 7: expected a number", err.to_string());
+    }
+
+    #[test]
+    fn in_file_adds_file_to_position() {
+        let err = Error::parse(3, 7, "expected a number").in_file("inputs/day06.txt");
+
+        assert_eq!("inputs/day06.txt:3:7: expected a number", err.to_string());
+    }
+
+    #[test]
+    fn in_file_keeps_existing_file() {
+        let err = Error::parse(1, 1, "bad")
+            .in_file("first.txt")
+            .in_file("second.txt");
+
+        assert_eq!("first.txt:1:1: bad", err.to_string());
+    }
+
+    #[test]
+    fn io_error_names_file() {
+        let err = Error::from(io::Error::new(io::ErrorKind::NotFound, "gone")).in_file("x.txt");
+
+        assert_eq!("Could not read 'x.txt': gone", err.to_string());
+    }
+}
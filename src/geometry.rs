@@ -0,0 +1,349 @@
+use std::cmp;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position on a plane where `x` grows to the right and `y` grows downwards
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    /// The distance walking only along the axes, like a taxi on a street grid
+    pub fn manhattan_distance(&self, other: &Point) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The distance when diagonal steps count the same as straight ones, like
+    /// a king on a chessboard
+    pub fn chebyshev_distance(&self, other: &Point) -> u32 {
+        cmp::max(self.x.abs_diff(other.x), self.y.abs_diff(other.y))
+    }
+
+    /// The straight line distance
+    pub fn euclidean_distance(&self, other: &Point) -> f64 {
+        let dx = f64::from(self.x) - f64::from(other.x);
+        let dy = f64::from(self.y) - f64::from(other.y);
+
+        dx.hypot(dy)
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Point {
+        Point { x, y }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, scale: i32) -> Point {
+        Point::new(self.x * scale, self.y * scale)
+    }
+}
+
+/// An axis-aligned rectangle covering `left..left + width` and
+/// `top..top + height`. The right and bottom edges are not part of it, so a
+/// rectangle with no width or height covers nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    pub fn new(left: i32, top: i32, width: i32, height: i32) -> Rect {
+        Rect {
+            left,
+            top,
+            width,
+            height,
+        }
+    }
+
+    /// The rectangle from `top_left` up to, but not including, `bottom_right`
+    pub fn from_corners(top_left: Point, bottom_right: Point) -> Rect {
+        Rect::new(
+            top_left.x,
+            top_left.y,
+            bottom_right.x - top_left.x,
+            bottom_right.y - top_left.y,
+        )
+    }
+
+    /// The smallest rectangle holding every point, or `None` if there are
+    /// no points
+    pub fn bounding_box<I: IntoIterator<Item = Point>>(points: I) -> Option<Rect> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(cmp::min(min.x, p.x), cmp::min(min.y, p.y)),
+                Point::new(cmp::max(max.x, p.x), cmp::max(max.y, p.y)),
+            )
+        });
+
+        Some(Rect::from_corners(min, max + Point::new(1, 1)))
+    }
+
+    /// The column just past the right edge
+    pub fn right(&self) -> i32 {
+        self.left + self.width
+    }
+
+    /// The row just past the bottom edge
+    pub fn bottom(&self) -> i32 {
+        self.top + self.height
+    }
+
+    pub fn top_left(&self) -> Point {
+        Point::new(self.left, self.top)
+    }
+
+    /// The corner just outside the rectangle, diagonally from `top_left`
+    pub fn bottom_right(&self) -> Point {
+        Point::new(self.right(), self.bottom())
+    }
+
+    pub fn area(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            u64::from(self.width as u32) * u64::from(self.height as u32)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.left
+            && point.x < self.right()
+            && point.y >= self.top
+            && point.y < self.bottom()
+    }
+
+    /// The part covered by both rectangles, if they overlap at all
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let top_left = Point::new(
+            cmp::max(self.left, other.left),
+            cmp::max(self.top, other.top),
+        );
+        let bottom_right = Point::new(
+            cmp::min(self.right(), other.right()),
+            cmp::min(self.bottom(), other.bottom()),
+        );
+
+        // checked before building the rect, since far apart corners could
+        // be too far apart for a width or height
+        if top_left.x >= bottom_right.x || top_left.y >= bottom_right.y {
+            return None;
+        }
+
+        Some(Rect::from_corners(top_left, bottom_right))
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The smallest rectangle covering both, or `None` if it's too wide or
+    /// tall to fit in an `i32`. An empty rectangle adds nothing.
+    pub fn union(&self, other: &Rect) -> Option<Rect> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+
+        let left = cmp::min(self.left, other.left);
+        let top = cmp::min(self.top, other.top);
+        let right = cmp::max(self.right(), other.right());
+        let bottom = cmp::max(self.bottom(), other.bottom());
+
+        Some(Rect::new(
+            left,
+            top,
+            right.checked_sub(left)?,
+            bottom.checked_sub(top)?,
+        ))
+    }
+
+    /// Every point inside the rectangle, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (left, right) = (self.left, self.right());
+
+        (self.top..self.bottom()).flat_map(move |y| (left..right).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn point_arithmetic() {
+        let mut p = Point::new(1, 2) + Point::new(3, -4);
+        assert_eq!(Point::new(4, -2), p);
+
+        p -= Point::new(1, 1);
+        assert_eq!(Point::new(3, -3), p);
+        assert_eq!(Point::new(-6, 6), -p * 2);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, 1);
+        let b = Point::new(4, -3);
+
+        assert_eq!(7, a.manhattan_distance(&b));
+        assert_eq!(4, a.chebyshev_distance(&b));
+        assert_eq!(5.0, a.euclidean_distance(&b));
+    }
+
+    #[test]
+    fn manhattan_distance_does_not_overflow() {
+        let a = Point::new(i32::MIN, 0);
+        let b = Point::new(i32::MAX, 0);
+
+        assert_eq!(u32::MAX, a.manhattan_distance(&b));
+    }
+
+    #[test]
+    fn contains_excludes_far_edges() {
+        let rect = Rect::new(1, 3, 4, 4);
+
+        assert!(rect.contains(Point::new(1, 3)));
+        assert!(rect.contains(Point::new(4, 6)));
+        assert!(!rect.contains(Point::new(5, 6)));
+        assert!(!rect.contains(Point::new(4, 7)));
+    }
+
+    #[test]
+    fn intersection_of_overlapping_rects() {
+        let a = Rect::new(1, 3, 4, 4);
+        let b = Rect::new(3, 1, 4, 4);
+
+        assert_eq!(Some(Rect::new(3, 3, 2, 2)), a.intersection(&b));
+        assert_eq!(4, a.intersection(&b).unwrap().area());
+    }
+
+    #[test]
+    fn touching_rects_do_not_intersect() {
+        let a = Rect::new(1, 3, 4, 4);
+        let c = Rect::new(5, 5, 2, 2);
+
+        assert_eq!(None, a.intersection(&c));
+        assert!(!a.intersects(&c));
+    }
+
+    #[test]
+    fn far_apart_rects_do_not_intersect() {
+        let a = Rect::new(-2_000_000_000, 0, 10, 10);
+        let b = Rect::new(2_000_000_000, 0, 10, 10);
+
+        assert_eq!(None, a.intersection(&b));
+        assert!(!b.intersects(&a));
+    }
+
+    #[test]
+    fn rects_at_extreme_coordinates() {
+        let low = Rect::new(i32::MIN, i32::MIN, 5, 5);
+        let high = Rect::new(i32::MAX - 5, i32::MAX - 5, 5, 5);
+        let wide = Rect::new(i32::MIN, 0, i32::MAX, 1);
+
+        assert_eq!(None, low.intersection(&high));
+        assert_eq!(
+            Some(Rect::new(i32::MIN, 0, 5, 1)),
+            wide.intersection(&Rect::new(i32::MIN, -5, 5, 10))
+        );
+        assert_eq!(None, low.union(&high));
+    }
+
+    #[test]
+    fn union_covers_both() {
+        let a = Rect::new(1, 3, 4, 4);
+        let c = Rect::new(5, 5, 2, 2);
+
+        assert_eq!(Some(Rect::new(1, 3, 6, 4)), a.union(&c));
+        assert_eq!(Some(c), Rect::default().union(&c));
+    }
+
+    #[test]
+    fn bounding_box_includes_every_point() {
+        let points = vec![Point::new(1, 1), Point::new(8, 3), Point::new(3, 9)];
+        let rect = Rect::bounding_box(points.iter().cloned()).unwrap();
+
+        assert_eq!(Rect::new(1, 1, 8, 9), rect);
+        assert!(points.iter().all(|p| rect.contains(*p)));
+        assert_eq!(None, Rect::bounding_box(vec![]));
+    }
+
+    #[test]
+    fn points_are_row_by_row() {
+        let points: Vec<Point> = Rect::new(2, 5, 2, 2).points().collect();
+
+        assert_eq!(
+            vec![
+                Point::new(2, 5),
+                Point::new(3, 5),
+                Point::new(2, 6),
+                Point::new(3, 6)
+            ],
+            points
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use std::ops::{Index, IndexMut};
+
+/// A rectangle of cells addressed by `(x, y)`, where `x` counts columns from
+/// the left and `y` counts rows from the top. Cells are stored row by row in a
+/// single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Offsets to the cells above, left, right and below
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets to every cell touching a cell, diagonals included, in reading order
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl<T: Clone> Grid<T> {
+    /// A grid with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// A grid filled by calling `f(x, y)` for each cell
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Grid<T>
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Checks if `(x, y)` is inside the grid
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the value of a cell. Returns false, changing nothing, if the
+    /// cell is outside the grid.
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> bool {
+        match self.get_mut(x, y) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The cells directly above, left, right and below, leaving out any that
+    /// fall outside the grid
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(x, y, &ORTHOGONAL)
+    }
+
+    /// Every cell touching `(x, y)`, diagonals included, leaving out any that
+    /// fall outside the grid
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(x, y, &SURROUNDING)
+    }
+
+    fn offsets(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let nx = offset(x, dx)?;
+            let ny = offset(y, dy)?;
+
+            if nx < width && ny < height {
+                Some((nx, ny))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The cells of row `y`, left to right
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    /// The cells of column `x`, top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// Every row, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// Every cell in row order, without positions
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+}
+
+fn offset(value: usize, by: isize) -> Option<usize> {
+    if by < 0 {
+        value.checked_sub(by.unsigned_abs())
+    } else {
+        value.checked_add(by as usize)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            self.contains(x, y),
+            "({}, {}) is outside a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            self.contains(x, y),
+            "({}, {}) is outside a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn numbered() -> Grid<usize> {
+        Grid::from_fn(3, 2, |x, y| y * 10 + x)
+    }
+
+    #[test]
+    fn get_is_bounds_checked() {
+        let grid = numbered();
+
+        assert_eq!(Some(&12), grid.get(2, 1));
+        assert_eq!(None, grid.get(3, 0));
+        assert_eq!(None, grid.get(0, 2));
+    }
+
+    #[test]
+    fn set_only_changes_cells_inside() {
+        let mut grid = Grid::new(2, 2, 0);
+
+        assert!(grid.set(1, 0, 5));
+        assert!(!grid.set(2, 0, 5));
+        assert_eq!(&[0, 5, 0, 0], grid.cells());
+    }
+
+    #[test]
+    fn index_uses_x_then_y() {
+        let mut grid = numbered();
+        grid[(2, 0)] += 100;
+
+        assert_eq!(102, grid[(2, 0)]);
+        assert_eq!(10, grid[(0, 1)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_outside_panics() {
+        let grid = numbered();
+        let _ = grid[(3, 0)];
+    }
+
+    #[test]
+    fn neighbours4_skips_edges() {
+        let grid = numbered();
+
+        let corner: Vec<_> = grid.neighbours4(0, 0).collect();
+        assert_eq!(vec![(1, 0), (0, 1)], corner);
+
+        let middle: Vec<_> = grid.neighbours4(1, 1).collect();
+        assert_eq!(vec![(1, 0), (0, 1), (2, 1)], middle);
+    }
+
+    #[test]
+    fn neighbours8_includes_diagonals() {
+        let grid = numbered();
+        let around: Vec<_> = grid.neighbours8(1, 0).collect();
+
+        assert_eq!(vec![(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)], around);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = numbered();
+
+        assert_eq!(Some(&[10, 11, 12][..]), grid.row(1));
+        assert_eq!(None, grid.row(2));
+        assert_eq!(vec![&1, &11], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(0, grid.column(3).count());
+        assert_eq!(2, grid.rows().count());
+    }
+
+    #[test]
+    fn iter_gives_positions() {
+        let grid = numbered();
+        let last = grid.iter().last().unwrap();
+
+        assert_eq!(((2, 1), &12), last);
+    }
+}
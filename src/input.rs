@@ -0,0 +1,203 @@
+use std::env;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use error::{Error, Result};
+
+/// Environment variable that overrides the directory puzzle inputs are read from
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Directory puzzle inputs are read from when nothing else is configured
+pub const DEFAULT_INPUT_DIR: &str = "inputs";
+
+/// Finds the puzzle input file for a day.
+///
+/// Inputs are named `dayNN.txt` inside a single directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputLocator {
+    dir: PathBuf,
+}
+
+impl InputLocator {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> InputLocator {
+        InputLocator { dir: dir.into() }
+    }
+
+    /// Picks the input directory, preferring an explicit override (like a
+    /// command line flag), then `AOC_INPUT_DIR`, then `./inputs`
+    pub fn from_env(dir_override: Option<&Path>) -> InputLocator {
+        if let Some(dir) = dir_override {
+            return InputLocator::new(dir);
+        }
+
+        match env::var_os(INPUT_DIR_VAR) {
+            Some(ref dir) if !dir.is_empty() => InputLocator::new(dir),
+            _ => InputLocator::new(DEFAULT_INPUT_DIR),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The path the input for a day is expected at, whether or not it exists
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{:02}.txt", day))
+    }
+
+    /// The path to the input for a day, if the file exists
+    pub fn locate(&self, day: u32) -> Result<PathBuf> {
+        let path = self.path(day);
+
+        if path.is_file() {
+            Ok(path)
+        } else {
+            Err(Error::MissingInput { day, path })
+        }
+    }
+
+    /// Reads the whole input for a day
+    pub fn read(&self, day: u32) -> Result<String> {
+        read_file(self.locate(day)?)
+    }
+}
+
+impl Default for InputLocator {
+    fn default() -> InputLocator {
+        InputLocator::from_env(None)
+    }
+}
+
+/// The path to a day's input using the default locator
+pub fn path(day: u32) -> PathBuf {
+    InputLocator::default().path(day)
+}
+
+/// Reads a whole file, naming the file if that fails
+pub fn read_file<P: AsRef<Path>>(path: P) -> Result<String> {
+    fs::read_to_string(&path).map_err(|err| Error::from(err).in_file(path))
+}
+
+/// Opens a file for buffered reading, naming the file if that fails
+pub fn open<P: AsRef<Path>>(path: P) -> Result<BufReader<File>> {
+    File::open(&path)
+        .map(BufReader::new)
+        .map_err(|err| Error::from(err).in_file(path))
+}
+
+/// Reads everything left in a reader, like an open file or stdin
+pub fn read_from<R: BufRead>(mut reader: R) -> Result<String> {
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents)?;
+
+    Ok(contents)
+}
+
+/// Opens a file and hands it to a day's reader-based parser, naming the file
+/// in any error
+pub fn parse_file<P, T, F>(path: P, parse: F) -> Result<T>
+where
+    P: AsRef<Path>,
+    F: FnOnce(BufReader<File>) -> Result<T>,
+{
+    let reader = open(&path)?;
+    parse(reader).map_err(|err| err.in_file(path))
+}
+
+/// Parses each non-blank line of the input, reporting the line number of the
+/// first one that fails
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let mut result = vec![];
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let value = line
+            .parse()
+            .map_err(|err: T::Err| Error::parse(index + 1, 1, err.to_string()))?;
+        result.push(value);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn path_pads_day_number() {
+        let locator = InputLocator::new("puzzles");
+
+        assert_eq!(Path::new("puzzles").join("day03.txt"), locator.path(3));
+        assert_eq!(Path::new("puzzles").join("day25.txt"), locator.path(25));
+    }
+
+    #[test]
+    fn from_env_prefers_override() {
+        let locator = InputLocator::from_env(Some(Path::new("elsewhere")));
+
+        assert_eq!(Path::new("elsewhere"), locator.dir());
+    }
+
+    #[test]
+    fn locate_finds_existing_input() {
+        let path = InputLocator::new(DEFAULT_INPUT_DIR).locate(1).unwrap();
+
+        assert_eq!(Path::new("inputs").join("day01.txt"), path);
+    }
+
+    #[test]
+    fn read_file_names_missing_file() {
+        let err = read_file("no-such-dir/day01.txt").unwrap_err();
+
+        assert!(err.to_string().contains("no-such-dir"));
+    }
+
+    #[test]
+    fn read_from_reads_whole_reader() {
+        let contents = read_from("+1\n-2\n".as_bytes()).unwrap();
+
+        assert_eq!("+1\n-2\n", contents);
+    }
+
+    #[test]
+    fn parse_file_names_file_in_parse_errors() {
+        let err =
+            parse_file(path(9), |reader| parse_lines::<u32>(&read_from(reader)?)).unwrap_err();
+
+        assert!(err.to_string().contains("day09.txt:1:1:"));
+    }
+
+    #[test]
+    fn parse_lines_skips_blank_lines() {
+        let values: Vec<u32> = parse_lines("1\r\n\n2\n  \n3").unwrap();
+
+        assert_eq!(vec![1, 2, 3], values);
+    }
+
+    #[test]
+    fn parse_lines_reports_line_number() {
+        let err = parse_lines::<u32>("1\n\nx\n").unwrap_err();
+
+        assert!(err.to_string().starts_with("line 3, column 1:"));
+    }
+
+    #[test]
+    fn locate_reports_missing_input() {
+        let err = InputLocator::new("no-such-dir").locate(1).unwrap_err();
+        let message = err.to_string();
+
+        assert!(message.contains("day 1"));
+        assert!(message.contains("day01.txt"));
+    }
+}
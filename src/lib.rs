@@ -1,19 +1,72 @@
-extern crate regex;
-
-#[cfg(test)]
-#[macro_use]
-extern crate spectral;
-
-#[macro_use]
-extern crate lazy_static;
-extern crate chrono;
-
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
+extern crate regex;
+
+#[cfg(test)]
+#[macro_use]
+extern crate spectral;
+
+#[macro_use]
+extern crate lazy_static;
+extern crate chrono;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod runner;
+pub mod scaffold;
+pub mod solver;
+
+#[cfg(test)]
+mod test_util;
+
+pub use error::{Error, Result};
+use solver::Solution;
+
+/// Every implemented day, in calendar order
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+];
+
+/// Looks up the solution for a single day
+pub fn solution(day: u32) -> Option<&'static dyn Solution> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn solutions_are_in_calendar_order() {
+        let days: Vec<u32> = SOLUTIONS.iter().map(|s| s.day()).collect();
+        let expected: Vec<u32> = (1..days.len() as u32 + 1).collect();
+        assert_eq!(expected, days);
+    }
+
+    #[test]
+    fn solution_finds_day() {
+        assert_eq!(3, solution(3).unwrap().day());
+        assert!(solution(26).is_none());
+    }
+}
@@ -1,142 +1,158 @@
 extern crate adventofcode;
+use adventofcode::answers::{self, AnswerManifest};
+use adventofcode::bench::{self, DayBench};
+use adventofcode::cli::{
+    self, BenchOptions, Command, InputSource, NewDayOptions, RunOptions, VerifyOptions,
+};
+use adventofcode::input::{self, InputLocator};
+use adventofcode::runner::{self, DayRun};
+use adventofcode::scaffold;
 use std::env;
+use std::io;
+use std::path::Path;
+use std::process;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        panic!("Usage: adventofcode 01")
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    match command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::Run(options) => run(&options),
+        Command::Verify(options) => verify(&options),
+        Command::Bench(options) => run_bench(&options),
+        Command::NewDay(options) => new_day(&options),
     }
-
-    let day = args[1].parse();
-
-    match day {
-        Ok(1) => day_one(),
-        Ok(2) => day_two(),
-        Ok(3) => day_three(),
-        Ok(4) => day_four(),
-        Ok(5) => day_five(),
-        Ok(6) => day_six(),
-        Ok(7) => day_seven(),
-        Ok(8) => day_eight(),
-        Ok(9) => day_nine(),
-        Ok(d) => panic!(format!("Day {} not implemented.", d)),
-        Err(_) => panic!(format!("Can't parse '{}' as a day", args[1])),
-    }
-}
-
-fn day_one() {
-    let filename = "inputs\\day01.txt";
-    let result1 = adventofcode::day01::calculate_frequency(filename).unwrap();
-    println!("ending frequency: {}", result1);
-
-    let result2 = adventofcode::day01::find_first_duplicate_frequency(filename).unwrap();
-    println!("first dupe frequence: {}", result2);
 }
 
-fn day_two() {
-    let filename = "inputs\\day02.txt";
-    let boxes = adventofcode::day02::load_boxes(filename).unwrap();
-
-    let result1 = adventofcode::day02::checksum(&boxes);
-    println!("checksum: {}", result1);
+fn run(options: &RunOptions) {
+    let solutions: Vec<_> = adventofcode::SOLUTIONS
+        .iter()
+        .filter(|s| options.days.contains(s.day()))
+        .collect();
 
-    let (b1, b2) = adventofcode::day02::find_correct_pair(&boxes).unwrap();
-    let result2 = b1.common_letters_with(&b2);
-    println!("Common letters of correct boxes: {}", result2);
-}
-
-fn day_three() {
-    let claims = adventofcode::day03::read_input("inputs\\day03.txt").unwrap();
-    let result1 = adventofcode::day03::calculate_overlap_area(&claims);
-    println!("overlapping area: {}sq inches", result1);
-
-    let result2 = adventofcode::day03::find_non_overlapping_claim(&claims).unwrap();
-    println!("non-overlapped claim: {:?}", result2);
-}
+    if solutions.is_empty() {
+        eprintln!("Nothing is implemented for {}", options.days);
+        process::exit(1);
+    }
 
-fn day_four() {
-    let logs = adventofcode::day04::read_logs("inputs\\day04.txt").unwrap();
-    let guard_id = adventofcode::day04::find_sleepiest_guard(&logs);
-    let (minute, count) = adventofcode::day04::find_sleepiest_minute(&logs, guard_id);
-    println!(
-        "Guard #{} slept the most, particularly during minute {} for a total of {} days",
-        guard_id, minute, count
-    );
-
-    let (guard_id, minute) = adventofcode::day04::find_sleepiest_guard_minute(&logs);
-    println!(
-        "Guard #{} spent minute {} asleep more than any other guard.",
-        guard_id, minute
-    );
+    let locator = InputLocator::from_env(options.inputs_dir.as_ref().map(|d| d.as_path()));
+
+    let runs: Vec<DayRun> = solutions
+        .iter()
+        .map(|solution| {
+            let input = match options.input {
+                Some(InputSource::File(ref path)) => {
+                    input::read_file(path).map_err(|err| err.to_string())
+                }
+                Some(InputSource::Stdin) => read_stdin(),
+                None => locator.read(solution.day()).map_err(|err| err.to_string()),
+            };
+
+            match input {
+                Ok(input) => runner::run(**solution, &input, options.part),
+                Err(err) => DayRun::failed(**solution, err),
+            }
+        })
+        .collect();
+
+    print!("{}", runner::render(&runs, options.format));
+
+    if !runs.iter().all(|run| run.is_ok()) {
+        process::exit(1);
+    }
 }
 
-fn day_five() {
-    let input = adventofcode::day05::read_file("inputs\\day05.txt").unwrap();
-
-    let result1 = adventofcode::day05::compress_polarities(&input);
-    println!(
-        "After fully reacting, the polymer is {} units long.",
-        result1.len()
-    );
-
-    let (unit, length) = adventofcode::day05::find_shortest(&input);
-    println!(
-        "Afer removing all the '{}' units, the fully reacted polymer is {} units long.",
-        unit, length
-    );
+fn verify(options: &VerifyOptions) {
+    let answers_file = options
+        .answers
+        .as_ref()
+        .map(|p| p.as_path())
+        .unwrap_or_else(|| Path::new(answers::DEFAULT_ANSWERS_FILE));
+
+    let manifest = match AnswerManifest::load(answers_file) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+
+    let locator = InputLocator::from_env(options.inputs_dir.as_ref().map(|d| d.as_path()));
+
+    let checks: Vec<_> = adventofcode::SOLUTIONS
+        .iter()
+        .filter(|s| options.days.contains(s.day()))
+        .flat_map(|solution| {
+            let run = match locator.read(solution.day()) {
+                Ok(input) => runner::run(*solution, &input, None),
+                Err(err) => DayRun::failed(*solution, err),
+            };
+            manifest.check(&run)
+        })
+        .collect();
+
+    print!("{}", answers::render_checks(&checks));
+
+    if checks.iter().any(|check| check.verdict.is_failure()) {
+        process::exit(1);
+    }
 }
 
-fn day_six() {
-    let input = adventofcode::day06::read_input("inputs\\day06.txt").unwrap();
-
-    let ((x, y), size) = adventofcode::day06::find_max_enclosed_area(&input);
-    println!(
-        "Max enclosed area is around point ({}, {}) and has size {}",
-        x, y, size
-    );
-
-    let result2 = adventofcode::day06::find_area_of_min_region(&input, 10_0000);
-    println!(
-        "The region less than 10k manahattan distance has {} points",
-        result2
-    );
-}
+fn run_bench(options: &BenchOptions) {
+    let locator = InputLocator::from_env(options.inputs_dir.as_ref().map(|d| d.as_path()));
+
+    let benches: Vec<DayBench> = adventofcode::SOLUTIONS
+        .iter()
+        .filter(|s| options.days.contains(s.day()))
+        .map(|solution| match locator.read(solution.day()) {
+            Ok(input) => bench::bench(*solution, &input, options.part, options.runs),
+            Err(err) => DayBench::failed(*solution, options.runs, err),
+        })
+        .collect();
+
+    if benches.is_empty() {
+        eprintln!("Nothing is implemented for {}", options.days);
+        process::exit(1);
+    }
 
-fn day_seven() {
-    let input = adventofcode::day07::read_input("inputs\\day07.txt").unwrap();
-    let order = adventofcode::day07::topological_sort(&input);
-    let time = adventofcode::day07::work_length(&input, 5, 60);
+    print!("{}", bench::render(&benches, options.format));
 
-    println!("You can process them in this order: {}", order);
-    println!(
-        "If it takes a base cost of 60 seconds, with 5 workers it will take {}s to complete",
-        time
-    );
+    if !benches.iter().all(|bench| bench.is_ok()) {
+        process::exit(1);
+    }
 }
 
-fn day_eight() {
-    let input = adventofcode::day08::read_file("inputs\\day08.txt");
-    let checksum = adventofcode::day08::metadata_checksum(&input);
-    let value = adventofcode::day08::calculate_node_value(&input);
-
-    println!("The checksum of the license file is {}.", checksum);
-    println!("The vale of the license file is {}", value);
+fn new_day(options: &NewDayOptions) {
+    let src_dir = options
+        .src_dir
+        .as_ref()
+        .map(|d| d.as_path())
+        .unwrap_or_else(|| Path::new(scaffold::DEFAULT_SRC_DIR));
+    let title = options
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Day {}", options.day));
+
+    match scaffold::new_day(src_dir, options.day, &title) {
+        Ok(written) => {
+            for path in written.iter() {
+                println!("wrote {}", path.display());
+            }
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
 }
 
-fn day_nine() {
-    const PLAYERS: usize = 486;
-    const PART1_LAST_MARBLE: u32 = 70833;
-    const PART2_LAST_MARBLE: u32 = PART1_LAST_MARBLE * 100;
-
-    let score1 = adventofcode::day09::winning_score(PLAYERS, PART1_LAST_MARBLE);
-    println!(
-        "With {} players playing {} marbles, the winner will have a score of {}.",
-        PLAYERS, PART1_LAST_MARBLE, score1
-    );
-
-    let score2 = adventofcode::day09::winning_score(PLAYERS, PART2_LAST_MARBLE);
-    println!(
-        "With {} players playing {} marbles, the winner will have a score of {}.",
-        PLAYERS, PART2_LAST_MARBLE, score2
-    );
+fn read_stdin() -> Result<String, String> {
+    let stdin = io::stdin();
+    input::read_from(stdin.lock()).map_err(|err| format!("Could not read stdin: {}", err))
 }
@@ -0,0 +1,279 @@
+use std::fmt::Display;
+use std::fmt::Write;
+use std::time::Duration;
+
+use solver::{Answers, Part, Solution, Timings};
+
+/// How the results of a run are printed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+/// The outcome of running one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayRun {
+    pub day: u32,
+    pub title: &'static str,
+    pub outcome: Result<Answers, String>,
+    pub timings: Option<Timings>,
+}
+
+impl DayRun {
+    /// A day that could not be run at all, like when its input is missing
+    pub fn failed<E: Display>(solution: &dyn Solution, err: E) -> DayRun {
+        DayRun {
+            day: solution.day(),
+            title: solution.title(),
+            outcome: Err(err.to_string()),
+            timings: None,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+/// Solves one day, capturing any failure instead of stopping
+pub fn run(solution: &dyn Solution, input: &str, part: Option<Part>) -> DayRun {
+    match solution.solve_timed(input, part) {
+        Ok((answers, timings)) => DayRun {
+            day: solution.day(),
+            title: solution.title(),
+            outcome: Ok(answers),
+            timings: Some(timings),
+        },
+        Err(err) => DayRun::failed(solution, err),
+    }
+}
+
+pub fn render(runs: &[DayRun], format: Format) -> String {
+    match format {
+        Format::Text => render_text(runs),
+        Format::Json => render_json(runs),
+    }
+}
+
+fn render_text(runs: &[DayRun]) -> String {
+    let mut out = String::new();
+
+    for run in runs.iter() {
+        writeln!(out, "Day {}: {}", run.day, run.title).unwrap();
+        let timings = run.timings.unwrap_or_default();
+
+        match run.outcome {
+            Ok(ref answers) => {
+                writeln!(out, "parsed in {}", format_duration(timings.parse)).unwrap();
+                if let Some(ref answer) = answers.part1 {
+                    write_answer(&mut out, 1, answer, timings.part1);
+                }
+                if let Some(ref answer) = answers.part2 {
+                    write_answer(&mut out, 2, answer, timings.part2);
+                }
+            }
+            Err(ref err) => writeln!(out, "error: {}", err).unwrap(),
+        }
+    }
+
+    out
+}
+
+fn write_answer(out: &mut String, part: u32, answer: &str, elapsed: Option<Duration>) {
+    match elapsed {
+        Some(elapsed) => writeln!(
+            out,
+            "part {}: {} ({})",
+            part,
+            answer,
+            format_duration(elapsed)
+        )
+        .unwrap(),
+        None => writeln!(out, "part {}: {}", part, answer).unwrap(),
+    }
+}
+
+fn render_json(runs: &[DayRun]) -> String {
+    let days: Vec<String> = runs
+        .iter()
+        .map(|run| {
+            let mut fields = vec![
+                format!("\"day\":{}", run.day),
+                format!("\"title\":{}", json_string(run.title)),
+            ];
+
+            match run.outcome {
+                Ok(ref answers) => {
+                    if let Some(ref answer) = answers.part1 {
+                        fields.push(format!("\"part1\":{}", json_string(answer)));
+                    }
+                    if let Some(ref answer) = answers.part2 {
+                        fields.push(format!("\"part2\":{}", json_string(answer)));
+                    }
+                }
+                Err(ref err) => fields.push(format!("\"error\":{}", json_string(err))),
+            }
+
+            if let Some(timings) = run.timings {
+                fields.push(format!("\"timings\":{}", json_timings(&timings)));
+            }
+
+            format!("{{{}}}", fields.join(","))
+        })
+        .collect();
+
+    format!("[{}]\n", days.join(","))
+}
+
+fn json_timings(timings: &Timings) -> String {
+    let mut fields = vec![format!("\"parse_ns\":{}", nanos(timings.parse))];
+
+    if let Some(elapsed) = timings.part1 {
+        fields.push(format!("\"part1_ns\":{}", nanos(elapsed)));
+    }
+    if let Some(elapsed) = timings.part2 {
+        fields.push(format!("\"part2_ns\":{}", nanos(elapsed)));
+    }
+
+    format!("{{{}}}", fields.join(","))
+}
+
+pub fn nanos(duration: Duration) -> u64 {
+    duration.as_secs() * 1_000_000_000 + u64::from(duration.subsec_nanos())
+}
+
+/// Formats a duration with a unit that keeps the number readable, like
+/// "512ns", "1.25ms" or "3.40s"
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = nanos(duration);
+
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+/// Quotes a string for JSON output
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use day01::Day01;
+
+    fn sample_runs() -> Vec<DayRun> {
+        vec![
+            DayRun {
+                day: 1,
+                title: "Chronal Calibration",
+                outcome: Ok(Answers {
+                    part1: Some(String::from("3")),
+                    part2: Some(String::from("2")),
+                }),
+                timings: Some(Timings {
+                    parse: Duration::from_micros(1500),
+                    part1: Some(Duration::from_nanos(20)),
+                    part2: Some(Duration::from_millis(2500)),
+                }),
+            },
+            DayRun {
+                day: 1,
+                title: "Chronal Calibration",
+                outcome: Ok(Answers {
+                    part1: None,
+                    part2: Some(String::from("2")),
+                }),
+                timings: Some(Timings {
+                    parse: Duration::from_nanos(10),
+                    part1: None,
+                    part2: Some(Duration::from_nanos(30)),
+                }),
+            },
+            DayRun::failed(&Day01, "no input"),
+        ]
+    }
+
+    #[test]
+    fn run_solves_selected_parts() {
+        let both = run(&Day01, "+1\n-2\n+3\n+1\n", None);
+        let second = run(&Day01, "+1\n-2\n+3\n+1\n", Some(Part::Two));
+
+        assert_eq!(sample_runs()[0].outcome, both.outcome);
+        assert_eq!(sample_runs()[1].outcome, second.outcome);
+    }
+
+    #[test]
+    fn run_times_selected_parts() {
+        let timings = run(&Day01, "+1\n-2\n", Some(Part::One)).timings.unwrap();
+
+        assert!(timings.part1.is_some());
+        assert!(timings.part2.is_none());
+    }
+
+    #[test]
+    fn render_text_lists_answers_timings_and_errors() {
+        let text = render(&sample_runs(), Format::Text);
+
+        assert_eq!(
+            "Day 1: Chronal Calibration\n\
+             parsed in 1.50ms\n\
+             part 1: 3 (20ns)\n\
+             part 2: 2 (2.50s)\n\
+             Day 1: Chronal Calibration\n\
+             parsed in 10ns\n\
+             part 2: 2 (30ns)\n\
+             Day 1: Chronal Calibration\n\
+             error: no input\n",
+            text
+        );
+    }
+
+    #[test]
+    fn render_json_is_an_array_of_days() {
+        let json = render(&sample_runs()[1..], Format::Json);
+
+        assert_eq!(
+            "[{\"day\":1,\"title\":\"Chronal Calibration\",\"part2\":\"2\",\
+             \"timings\":{\"parse_ns\":10,\"part2_ns\":30}},\
+             {\"day\":1,\"title\":\"Chronal Calibration\",\"error\":\"no input\"}]\n",
+            json
+        );
+    }
+
+    #[test]
+    fn format_duration_picks_unit() {
+        assert_eq!("999ns", format_duration(Duration::from_nanos(999)));
+        assert_eq!("12.35µs", format_duration(Duration::from_nanos(12_345)));
+        assert_eq!("1.00ms", format_duration(Duration::from_millis(1)));
+        assert_eq!("61.00s", format_duration(Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn json_string_escapes() {
+        assert_eq!("\"a\\\"b\\\\c\\n\"", json_string("a\"b\\c\n"));
+    }
+}
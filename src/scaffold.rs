@@ -0,0 +1,369 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use error::{Error, Result};
+
+/// Where the crate's source lives unless told otherwise
+pub const DEFAULT_SRC_DIR: &str = "src";
+
+/// The module for a new day. `%DAYNN%`, `%DAYN%` and `%TITLE%` are filled
+/// in by `module_source`.
+const MODULE_TEMPLATE: &str = r#"mod entry;
+
+pub use self::entry::Entry;
+
+use std::io::BufRead;
+use std::path::Path;
+
+use error::{Error, Result};
+use input;
+use solver::Solver;
+
+pub struct Day%DAYNN%;
+
+impl Solver for Day%DAYNN% {
+    type Input = Vec<Entry>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u32 = %DAYN%;
+    const TITLE: &'static str = "%TITLE%";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(&self, entries: &Self::Input) -> Result<Self::Part1> {
+        solve_part1(entries)
+    }
+
+    fn part2(&self, entries: &Self::Input) -> Result<Self::Part2> {
+        solve_part2(entries)
+    }
+}
+
+pub fn read_input<P: AsRef<Path>>(filename: P) -> Result<Vec<Entry>> {
+    input::parse_file(filename, read_from)
+}
+
+/// Reads entries from anything buffered, like an open file or stdin
+pub fn read_from<R: BufRead>(reader: R) -> Result<Vec<Entry>> {
+    parse_input(&input::read_from(reader)?)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Entry>> {
+    input::parse_lines(input)
+}
+
+pub fn solve_part1(_entries: &[Entry]) -> Result<usize> {
+    Err(Error::domain("Part 1 of day %DAYN% is not solved yet"))
+}
+
+pub fn solve_part2(_entries: &[Entry]) -> Result<usize> {
+    Err(Error::domain("Part 2 of day %DAYN% is not solved yet"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use input;
+
+    const SAMPLE: &str = "example
+";
+
+    #[test]
+    fn read_from_sample() {
+        let entries = read_from(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(1, entries.len());
+    }
+
+    // drop the #[ignore]s once each part is solved and its answer is known
+    #[test]
+    #[ignore]
+    fn part1_sample() {
+        let entries = read_from(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(0, solve_part1(&entries).unwrap());
+    }
+
+    #[test]
+    #[ignore]
+    fn part1_input() {
+        let entries = read_input(input::path(%DAYN%)).unwrap();
+        assert_eq!(0, solve_part1(&entries).unwrap());
+    }
+
+    #[test]
+    #[ignore]
+    fn part2_sample() {
+        let entries = read_from(SAMPLE.as_bytes()).unwrap();
+        assert_eq!(0, solve_part2(&entries).unwrap());
+    }
+
+    #[test]
+    #[ignore]
+    fn part2_input() {
+        let entries = read_input(input::path(%DAYN%)).unwrap();
+        assert_eq!(0, solve_part2(&entries).unwrap());
+    }
+}
+"#;
+
+/// The parser for one line of a new day's input
+const ENTRY_TEMPLATE: &str = r#"use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// One line of the puzzle input
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub text: String,
+}
+
+impl FromStr for Entry {
+    type Err = ParseEntryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+
+        if text.is_empty() {
+            return Err(ParseEntryError::Empty);
+        }
+
+        Ok(Entry {
+            text: String::from(text),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseEntryError {
+    Empty,
+}
+
+impl fmt::Display for ParseEntryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseEntryError::Empty => f.write_str("Entry is empty"),
+        }
+    }
+}
+
+impl error::Error for ParseEntryError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_success() {
+        let entry: Entry = "example".parse().unwrap();
+
+        assert_eq!("example", entry.text);
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert!("  ".parse::<Entry>().is_err());
+    }
+}
+"#;
+
+/// Adds a new day to the crate in `src_dir`: its module, a `FromStr` parser
+/// in a submodule, and its entries in `lib.rs`. Nothing is written if the day
+/// already exists. Returns the files that were written.
+pub fn new_day<P: AsRef<Path>>(src_dir: P, day: u32, title: &str) -> Result<Vec<PathBuf>> {
+    let src_dir = src_dir.as_ref();
+    let lib_path = src_dir.join("lib.rs");
+    let module_dir = src_dir.join(format!("day{:02}", day));
+
+    if module_dir.exists() {
+        return Err(Error::domain(format!(
+            "'{}' already exists",
+            module_dir.display()
+        )));
+    }
+
+    let lib = ::input::read_file(&lib_path)?;
+    let lib = register(&lib, day).map_err(|err| err.in_file(&lib_path))?;
+
+    let module_path = module_dir.join("mod.rs");
+    let entry_path = module_dir.join("entry.rs");
+
+    fs::create_dir_all(&module_dir).map_err(|err| Error::from(err).in_file(&module_dir))?;
+    write(&module_path, &module_source(day, title))?;
+    write(&entry_path, ENTRY_TEMPLATE)?;
+    write(&lib_path, &lib)?;
+
+    Ok(vec![module_path, entry_path, lib_path])
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).map_err(|err| Error::from(err).in_file(path))
+}
+
+/// The source of a new day's module
+pub fn module_source(day: u32, title: &str) -> String {
+    let title = title.replace('\\', "\\\\").replace('"', "\\\"");
+
+    MODULE_TEMPLATE
+        .replace("%DAYNN%", &format!("{:02}", day))
+        .replace("%DAYN%", &day.to_string())
+        .replace("%TITLE%", &title)
+}
+
+/// The source of a new day's line parser
+pub fn entry_source() -> &'static str {
+    ENTRY_TEMPLATE
+}
+
+/// Adds a day to the source of `lib.rs`: a `pub mod dayNN;` declaration
+/// next to the other days, and its solution in `SOLUTIONS`, both kept in
+/// calendar order
+pub fn register(lib: &str, day: u32) -> Result<String> {
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+    let name = format!("day{:02}", day);
+
+    let module_line = format!("pub mod {};", name);
+    let modules = insert_in_order(&mut lines, day, &module_line, |line| {
+        day_number(line.strip_prefix("pub mod day")?.strip_suffix(';')?)
+    });
+
+    let solution_line = format!("    &{}::Day{:02},", name, day);
+    let solutions = insert_in_order(&mut lines, day, &solution_line, |line| {
+        let line = line.trim().strip_prefix("&day")?;
+        day_number(&line[..line.find("::")?])
+    });
+
+    match (modules, solutions) {
+        (Some(false), Some(false)) => {
+            let mut lib = lines.join("\n");
+            lib.push('\n');
+            Ok(lib)
+        }
+        (Some(true), _) | (_, Some(true)) => Err(Error::domain(format!(
+            "Day {} is already registered in lib.rs",
+            day
+        ))),
+        _ => Err(Error::domain(
+            "Can't find the day modules and SOLUTIONS in lib.rs",
+        )),
+    }
+}
+
+/// Puts `new_line` next to the existing lines for other days, found with
+/// `day_of`. Returns `None` when there are no such lines, and `Some(true)`
+/// without changing anything when the day is already there.
+fn insert_in_order<F>(lines: &mut Vec<String>, day: u32, new_line: &str, day_of: F) -> Option<bool>
+where
+    F: Fn(&str) -> Option<u32>,
+{
+    let days: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| day_of(line).map(|d| (index, d)))
+        .collect();
+
+    if days.iter().any(|&(_, d)| d == day) {
+        return Some(true);
+    }
+
+    let index = match days.iter().find(|&&(_, d)| d > day) {
+        Some(&(index, _)) => index,
+        None => days.last()?.0 + 1,
+    };
+
+    lines.insert(index, String::from(new_line));
+    Some(false)
+}
+
+fn day_number(s: &str) -> Option<u32> {
+    if s.len() == 2 && s.chars().all(|c| c.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    const LIB: &str = "pub mod day01;
+pub mod day03;
+
+pub mod solver;
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day03::Day03,
+];
+";
+
+    #[test]
+    fn register_keeps_calendar_order() {
+        let lib = register(LIB, 2).unwrap();
+
+        assert_eq!(
+            "pub mod day01;
+pub mod day02;
+pub mod day03;
+
+pub mod solver;
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+];
+",
+            lib
+        );
+    }
+
+    #[test]
+    fn register_appends_later_days() {
+        let lib = register(LIB, 10).unwrap();
+
+        assert!(lib.contains("pub mod day03;\npub mod day10;\n"));
+        assert!(lib.contains("    &day03::Day03,\n    &day10::Day10,\n];"));
+    }
+
+    #[test]
+    fn register_rejects_existing_day() {
+        assert!(register(LIB, 3).is_err());
+    }
+
+    #[test]
+    fn register_needs_days_to_follow() {
+        assert!(register("pub mod solver;\n", 1).is_err());
+    }
+
+    #[test]
+    fn module_source_fills_in_day_and_title() {
+        let source = module_source(7, "The \"Sum\" of Its Parts");
+
+        assert!(source.contains("pub struct Day07;"));
+        assert!(source.contains("const DAY: u32 = 7;"));
+        assert!(source.contains("const TITLE: &'static str = \"The \\\"Sum\\\" of Its Parts\";"));
+        assert!(source.contains("input::path(7)"));
+    }
+
+    #[test]
+    fn new_day_writes_module_and_registers_it() {
+        let src = env::temp_dir().join(format!("aoc-scaffold-{}", ::std::process::id()));
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), LIB).unwrap();
+
+        let written = new_day(&src, 4, "Repose Record").unwrap();
+        let again = new_day(&src, 4, "Repose Record");
+        let lib = fs::read_to_string(src.join("lib.rs")).unwrap();
+        let entry = fs::read_to_string(src.join("day04").join("entry.rs")).unwrap();
+        fs::remove_dir_all(&src).unwrap();
+
+        assert_eq!(3, written.len());
+        assert!(again.is_err());
+        assert!(lib.contains("pub mod day04;"));
+        assert_eq!(entry_source(), entry);
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use error::Result;
+
+/// A solution to a single day's puzzle.
+///
+/// The input is parsed once and then handed to each part, so the expensive
+/// work of reading the puzzle input is not repeated.
+pub trait Solver {
+    /// The parsed puzzle input shared by both parts
+    type Input;
+
+    /// The answer to part one
+    type Part1: Display;
+
+    /// The answer to part two
+    type Part2: Display;
+
+    /// The day of the advent calendar this solves
+    const DAY: u32;
+
+    /// The title of the puzzle
+    const TITLE: &'static str;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Part1>;
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Part2>;
+}
+
+/// One half of a day's puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Whether this part should run when `selected` was requested, where
+    /// `None` means both parts
+    pub fn is_selected(self, selected: Option<Part>) -> bool {
+        match selected {
+            Some(part) => part == self,
+            None => true,
+        }
+    }
+}
+
+/// The answers to a puzzle, formatted for display. A part that was not
+/// asked for is `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// How long each step of solving a puzzle took. A part that was not asked
+/// for is `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1.unwrap_or_default() + self.part2.unwrap_or_default()
+    }
+}
+
+/// An object safe view of a `Solver` so that every day can live in one list.
+pub trait Solution: Sync {
+    fn day(&self) -> u32;
+
+    fn title(&self) -> &'static str;
+
+    /// Parses the input and solves the selected part, or both parts if `part`
+    /// is `None`, timing each step
+    fn solve_timed(&self, input: &str, part: Option<Part>) -> Result<(Answers, Timings)>;
+
+    fn solve(&self, input: &str, part: Option<Part>) -> Result<Answers> {
+        let (answers, _) = self.solve_timed(input, part)?;
+        Ok(answers)
+    }
+}
+
+impl<S: Solver + Sync> Solution for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn solve_timed(&self, input: &str, part: Option<Part>) -> Result<(Answers, Timings)> {
+        let (input, parse) = timed(|| self.parse(input));
+        let input = input?;
+
+        let mut answers = Answers {
+            part1: None,
+            part2: None,
+        };
+        let mut timings = Timings {
+            parse,
+            part1: None,
+            part2: None,
+        };
+
+        if Part::One.is_selected(part) {
+            let (answer, elapsed) = timed(|| self.part1(&input));
+            answers.part1 = Some(answer?.to_string());
+            timings.part1 = Some(elapsed);
+        }
+
+        if Part::Two.is_selected(part) {
+            let (answer, elapsed) = timed(|| self.part2(&input));
+            answers.part2 = Some(answer?.to_string());
+            timings.part2 = Some(elapsed);
+        }
+
+        Ok((answers, timings))
+    }
+}
+
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+
+    (result, start.elapsed())
+}
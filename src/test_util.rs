@@ -0,0 +1,34 @@
+//! Helpers shared by the tests
+
+/// A small xorshift generator, so randomised tests are repeatable without
+/// pulling in a crate
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u32,
+}
+
+impl XorShift {
+    /// The seed must not be 0, or every number is 0
+    pub fn new(seed: u32) -> XorShift {
+        assert_ne!(0, seed, "a xorshift seed can't be 0");
+        XorShift { state: seed }
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state
+    }
+
+    /// A number from 0 up to but not including `n`
+    pub fn below(&mut self, n: u32) -> u32 {
+        self.next_u32() % n
+    }
+
+    /// A number from `lo` to `hi`, including both
+    pub fn between(&mut self, lo: i32, hi: i32) -> i32 {
+        let span = (i64::from(hi) - i64::from(lo) + 1) as u64;
+        (i64::from(lo) + (u64::from(self.next_u32()) % span) as i64) as i32
+    }
+}